@@ -0,0 +1,88 @@
+//! One square-and-multiply ladder shared by every exponentiation and scalar
+//! multiplication, written against the smallest structure it needs.
+
+/// Associative operation with a neutral element. The identity is taken from
+/// an existing element because it can depend on runtime parameters such as
+/// the prime or the curve.
+pub trait Monoid: Copy {
+    fn identity(&self) -> Self;
+    fn combine(self, other: Self) -> Self;
+
+    /// `self` combined with itself, for types with a faster formula.
+    fn double(self) -> Self {
+        self.combine(self)
+    }
+}
+
+pub trait Group: Monoid {
+    fn inverse(self) -> Self;
+}
+
+/// Views a type through its multiplication, so that `multiply` on it is
+/// exponentiation.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Multiplicative<T>(pub T);
+
+/// `element` combined with itself k times, where k has `bits` significant
+/// bits and `bit(i)` tells whether bit i is set. Goes from the most
+/// significant bit down, doubling at every step and combining with `element`
+/// on set bits.
+pub fn multiply<M: Monoid>(element: M, bits: u32, bit: impl Fn(u32) -> bool) -> M {
+    let mut result = element.identity();
+    for i in (0..bits).rev() {
+        result = result.double();
+        if bit(i) {
+            result = result.combine(element);
+        }
+    }
+
+    result
+}
+
+impl Monoid for Multiplicative<f32> {
+    fn identity(&self) -> Self {
+        Multiplicative(1.0)
+    }
+
+    fn combine(self, other: Self) -> Self {
+        Multiplicative(self.0 * other.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{field_element::GenericFieldElement, scalar::Scalar, u256::U256};
+
+    fn multiply_u32<M: Monoid>(element: M, k: u32) -> M {
+        multiply(element, u32::BITS - k.leading_zeros(), |i| {
+            k & (1 << i) != 0
+        })
+    }
+
+    #[test]
+    fn multiplying_by_zero_gives_identity() {
+        assert_eq!(multiply_u32(Scalar::from(5), 0), Scalar::ZERO);
+        assert_eq!(multiply_u32(Multiplicative(3.0f32), 0), Multiplicative(1.0));
+    }
+
+    #[test]
+    fn additive_scalars_multiply() {
+        assert_eq!(multiply_u32(Scalar::from(7), 1485), Scalar::from(7 * 1485));
+        let minus_one = Scalar::ONE.inverse();
+        assert_eq!(multiply_u32(minus_one, 3), Scalar::from(3).inverse());
+    }
+
+    #[test]
+    fn multiplicative_elements_exponentiate() {
+        let base = GenericFieldElement::<u64>::new(3u64, 13).unwrap();
+        assert_eq!(multiply_u32(Multiplicative(base), 3).0.number, 1);
+        assert_eq!(multiply_u32(Multiplicative(2.0f32), 10).0, 1024.0);
+
+        let exponent = U256::from(12345);
+        let power = multiply(Multiplicative(Scalar::from(2)), exponent.bits(), |i| {
+            exponent.bit(i)
+        });
+        assert_eq!(power.0, Scalar::from(2).pow(exponent));
+    }
+}
@@ -0,0 +1,199 @@
+//! Branch-free field arithmetic for code that handles secret values.
+//!
+//! The regular operators branch on the values being reduced (early exits in
+//! division, conditional subtractions), which leaks timing information. The
+//! functions here always do the same sequence of operations and use masked
+//! selection instead of branches. They are slower and only meant for secrets.
+
+use std::hint::black_box;
+
+use crate::{field_element::FieldElement, u256::U256};
+
+fn mask(choice: bool) -> u64 {
+    0u64.wrapping_sub(black_box(choice as u64))
+}
+
+impl U256 {
+    /// Returns `a` when `choice` is false and `b` when it is true, without branching.
+    pub fn ct_select(a: Self, b: Self, choice: bool) -> Self {
+        let mask = mask(choice);
+        let (a, b) = (a.limbs(), b.limbs());
+        let mut limbs = [0u64; 4];
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            *limb = a[i] ^ (mask & (a[i] ^ b[i]));
+        }
+
+        U256::from_limbs(limbs)
+    }
+
+    pub fn ct_eq(&self, other: &Self) -> bool {
+        let (a, b) = (self.limbs(), other.limbs());
+        let difference = (0..4).fold(0u64, |acc, i| acc | (a[i] ^ b[i]));
+        black_box(difference) == 0
+    }
+
+    pub fn ct_add_mod(self, other: Self, modulus: Self) -> Self {
+        let (sum, carry) = self.overflowing_add(other);
+        let (reduced, borrow) = sum.overflowing_sub(modulus);
+        U256::ct_select(sum, reduced, carry | !borrow)
+    }
+
+    pub fn ct_sub_mod(self, other: Self, modulus: Self) -> Self {
+        let (difference, borrow) = self.overflowing_sub(other);
+        let (wrapped, _) = difference.overflowing_add(modulus);
+        U256::ct_select(difference, wrapped, borrow)
+    }
+
+    /// Reduces the 512-bit product one bit at a time, with a masked
+    /// subtraction at every step regardless of the values involved.
+    pub fn ct_mul_mod(self, other: Self, modulus: Self) -> Self {
+        let (low, high) = self.widening_mul(other);
+        let mut remainder = U256::ZERO;
+
+        for i in (0..512).rev() {
+            let bit = if i >= 256 {
+                high.bit(i - 256)
+            } else {
+                low.bit(i)
+            };
+            let (doubled, carry) = remainder.overflowing_add(remainder);
+            let (shifted, _) = doubled.overflowing_add(U256::from(bit as u32));
+            let (reduced, borrow) = shifted.overflowing_sub(modulus);
+            remainder = U256::ct_select(shifted, reduced, carry | !borrow);
+        }
+
+        remainder
+    }
+
+    /// Square-and-always-multiply over all 256 exponent bits, so the exponent
+    /// may be secret as well.
+    pub fn ct_pow_mod(self, exponent: Self, modulus: Self) -> Self {
+        let base = self.ct_mul_mod(U256::ONE, modulus);
+        let mut result = U256::ONE % modulus;
+
+        for i in (0..256).rev() {
+            result = result.ct_mul_mod(result, modulus);
+            let product = result.ct_mul_mod(base, modulus);
+            result = U256::ct_select(result, product, exponent.bit(i));
+        }
+
+        result
+    }
+}
+
+impl FieldElement {
+    fn assert_same_field(&self, other: &Self) {
+        self.check_same_field(other)
+            .unwrap_or_else(|e| panic!("{}", e));
+    }
+
+    /// Returns `a` when `choice` is false and `b` when it is true, without branching.
+    pub fn conditional_select(a: &Self, b: &Self, choice: bool) -> Self {
+        a.assert_same_field(b);
+
+        Self {
+            number: U256::ct_select(a.number, b.number, choice),
+            prime: a.prime,
+        }
+    }
+
+    pub fn ct_eq(&self, other: &Self) -> bool {
+        self.prime == other.prime && self.number.ct_eq(&other.number)
+    }
+
+    pub fn ct_add(&self, other: &Self) -> Self {
+        self.assert_same_field(other);
+
+        Self {
+            number: self.number.ct_add_mod(other.number, self.prime),
+            prime: self.prime,
+        }
+    }
+
+    pub fn ct_sub(&self, other: &Self) -> Self {
+        self.assert_same_field(other);
+
+        Self {
+            number: self.number.ct_sub_mod(other.number, self.prime),
+            prime: self.prime,
+        }
+    }
+
+    pub fn ct_mul(&self, other: &Self) -> Self {
+        self.assert_same_field(other);
+
+        Self {
+            number: self.number.ct_mul_mod(other.number, self.prime),
+            prime: self.prime,
+        }
+    }
+
+    pub fn ct_pow(&self, exponent: U256) -> Self {
+        Self {
+            number: self.number.ct_pow_mod(exponent, self.prime),
+            prime: self.prime,
+        }
+    }
+
+    /// Inverse through Fermat's little theorem, a^(p-2). Unlike the extended
+    /// Euclidean algorithm the number of steps does not depend on the value.
+    /// Zero maps to zero.
+    pub fn ct_inverse(&self) -> Self {
+        self.ct_pow(self.prime - U256::from(2))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn secp256k1_element(hex: &str) -> FieldElement {
+        let prime =
+            U256::from_hex("fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f")
+                .unwrap();
+        FieldElement::new(U256::from_hex(hex).unwrap(), prime).unwrap()
+    }
+
+    #[test]
+    fn select_picks_operand_by_choice() {
+        let a = FieldElement::new(3, 7).unwrap();
+        let b = FieldElement::new(5, 7).unwrap();
+        assert_eq!(FieldElement::conditional_select(&a, &b, false), a);
+        assert_eq!(FieldElement::conditional_select(&a, &b, true), b);
+    }
+
+    #[test]
+    fn constant_time_operations_match_regular_operations() {
+        let prime = 223;
+        for x in 0..prime {
+            for y in [0, 1, 17, 111, 222] {
+                let a = FieldElement::new(x, prime).unwrap();
+                let b = FieldElement::new(y, prime).unwrap();
+                assert_eq!(a.ct_add(&b), a + b);
+                assert_eq!(a.ct_sub(&b), a - b);
+                assert_eq!(a.ct_mul(&b), a * b);
+                assert!(a.ct_eq(&a) && (x == y || !a.ct_eq(&b)));
+            }
+        }
+    }
+
+    #[test]
+    fn constant_time_operations_match_regular_operations_on_256_bits() {
+        let a =
+            secp256k1_element("e9bb466a287385820942dc06bc69f2658575062102fbcd4f357fbc5af71a1bfc");
+        let b =
+            secp256k1_element("25b2116aae6cff55ce0c3f08e12656f10e11160004524a7c3d2bd371fc80be13");
+        assert_eq!(a.ct_add(&b), a + b);
+        assert_eq!(b.ct_sub(&a), b - a);
+        assert_eq!(a.ct_mul(&b), a * b);
+        assert_eq!(a.ct_inverse(), a.inverse());
+    }
+
+    #[test]
+    #[should_panic]
+    fn cannot_mix_primes() {
+        let a = FieldElement::new(1, 7).unwrap();
+        let b = FieldElement::new(1, 11).unwrap();
+        let _ = a.ct_add(&b);
+    }
+}
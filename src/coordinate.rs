@@ -1,9 +1,12 @@
 use std::{
-    fmt::{Debug, Display},
+    fmt::{Debug, Display, Formatter, LowerHex, Result as FmtResult, UpperHex},
     ops::{Add, Div, Mul, Sub},
 };
 
-use crate::{field_element::FieldElement, is_zero::IsZero, pow::Pow, real_value::RealValue};
+use crate::{
+    field_element::GenericFieldElement, field_uint::FieldUint, fp::Fp, is_zero::IsZero, pow::Pow,
+    real_value::RealValue, s256_field::S256Field,
+};
 
 pub trait GraphPoint:
     Display
@@ -43,6 +46,7 @@ impl<
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum Coordinate<G: GraphPoint> {
     Value(G),
     Infinity,
@@ -72,8 +76,20 @@ impl From<RealValue> for Coordinate<RealValue> {
     }
 }
 
-impl From<FieldElement> for Coordinate<FieldElement> {
-    fn from(value: FieldElement) -> Self {
+impl<U: FieldUint> From<GenericFieldElement<U>> for Coordinate<GenericFieldElement<U>> {
+    fn from(value: GenericFieldElement<U>) -> Self {
+        Coordinate::Value(value)
+    }
+}
+
+impl<const P: u64> From<Fp<P>> for Coordinate<Fp<P>> {
+    fn from(value: Fp<P>) -> Self {
+        Coordinate::Value(value)
+    }
+}
+
+impl From<S256Field> for Coordinate<S256Field> {
+    fn from(value: S256Field) -> Self {
         Coordinate::Value(value)
     }
 }
@@ -101,13 +117,13 @@ impl<T: GraphPoint> Coordinate<T> {
     }
 }
 
-impl<T: GraphPoint> Pow for Coordinate<T> {
-    fn pow(&self, exp: i32) -> Self {
+impl<T: GraphPoint + Pow<E>, E> Pow<E> for Coordinate<T> {
+    fn pow(&self, exp: E) -> Self {
         if self.is_infinity() {
             return Coordinate::Infinity;
         }
 
-        return self.map(|x| x.pow(exp));
+        self.map(|x| x.pow(exp))
     }
 }
 
@@ -118,10 +134,10 @@ impl<T: GraphPoint> Add for Coordinate<T> {
 
     fn add(self, other: Self) -> Self::Output {
         match (self, other) {
-            (Coordinate::Value(x), Coordinate::Value(y)) =>Coordinate::Value(x + y),
+            (Coordinate::Value(x), Coordinate::Value(y)) => Coordinate::Value(x + y),
             (Coordinate::Value(_), Coordinate::Infinity) => self,
             (Coordinate::Infinity, Coordinate::Value(_)) => other,
-            _ => Coordinate::Infinity
+            _ => Coordinate::Infinity,
         }
     }
 }
@@ -131,9 +147,9 @@ impl<T: GraphPoint> Add<i32> for Coordinate<T> {
 
     fn add(self, other: i32) -> Self::Output {
         if let Coordinate::Value(x) = self {
-            return Coordinate::Value(x + other);
+            Coordinate::Value(x + other)
         } else {
-            return Coordinate::Infinity;
+            Coordinate::Infinity
         }
     }
 }
@@ -143,9 +159,9 @@ impl<T: GraphPoint> Sub for Coordinate<T> {
 
     fn sub(self, other: Self) -> Self::Output {
         if let (Coordinate::Value(x), Coordinate::Value(y)) = (self, other) {
-            return Coordinate::Value(x - y);
+            Coordinate::Value(x - y)
         } else {
-            return Coordinate::Infinity;
+            Coordinate::Infinity
         }
     }
 }
@@ -155,9 +171,9 @@ impl<T: GraphPoint> Mul for Coordinate<T> {
 
     fn mul(self, other: Self) -> Self::Output {
         if let (Coordinate::Value(x), Coordinate::Value(y)) = (self, other) {
-            return Coordinate::Value(x * y);
+            Coordinate::Value(x * y)
         } else {
-            return Coordinate::Infinity;
+            Coordinate::Infinity
         }
     }
 }
@@ -167,9 +183,9 @@ impl<T: GraphPoint> Mul<i32> for Coordinate<T> {
 
     fn mul(self, other: i32) -> Self::Output {
         if let Coordinate::Value(x) = self {
-            return Coordinate::Value(x * other);
+            Coordinate::Value(x * other)
         } else {
-            return Coordinate::Infinity;
+            Coordinate::Infinity
         }
     }
 }
@@ -179,9 +195,9 @@ impl<T: GraphPoint> Div for Coordinate<T> {
 
     fn div(self, other: Self) -> Self::Output {
         if let (Coordinate::Value(x), Coordinate::Value(y)) = (self, other) {
-            return Coordinate::Value(x / y);
+            Coordinate::Value(x / y)
         } else {
-            return Coordinate::Infinity;
+            Coordinate::Infinity
         }
     }
 }
@@ -219,3 +235,23 @@ impl<T: GraphPoint> Display for Coordinate<T> {
         }
     }
 }
+
+impl<T: GraphPoint + LowerHex> LowerHex for Coordinate<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        if let Coordinate::Value(v) = self {
+            LowerHex::fmt(v, f)
+        } else {
+            write!(f, "Inf")
+        }
+    }
+}
+
+impl<T: GraphPoint + UpperHex> UpperHex for Coordinate<T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        if let Coordinate::Value(v) = self {
+            UpperHex::fmt(v, f)
+        } else {
+            write!(f, "Inf")
+        }
+    }
+}
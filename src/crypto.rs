@@ -0,0 +1,5 @@
+//! Symmetric primitives and hash functions. These are written for
+//! readability and are not hardened against side channels.
+
+pub mod aead;
+pub mod sha256;
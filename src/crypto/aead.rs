@@ -0,0 +1,307 @@
+//! Authenticated encryption with associated data. ChaCha20-Poly1305
+//! (RFC 8439) is the only algorithm so far; consumers should go through the
+//! `Aead` trait so others can be added without changing call sites.
+
+use crate::{field_uint::FieldUint, u256::U256};
+
+pub type Nonce = [u8; 12];
+
+pub trait Aead {
+    const KEY_LEN: usize;
+    const TAG_LEN: usize;
+
+    /// Encrypts `plaintext` and appends the authentication tag.
+    fn seal(&self, nonce: &Nonce, aad: &[u8], plaintext: &[u8]) -> Vec<u8>;
+
+    /// Checks the tag at the end of `ciphertext` and decrypts the rest.
+    fn open(&self, nonce: &Nonce, aad: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>, String>;
+}
+
+/// Produces unique nonces for one key: a fixed 4-byte prefix followed by a
+/// little-endian 64-bit counter. A nonce must never be reused with the same
+/// key, so the sequence fails instead of wrapping around.
+#[derive(Debug, Clone)]
+pub struct NonceSequence {
+    prefix: [u8; 4],
+    counter: u64,
+}
+
+impl NonceSequence {
+    pub fn new(prefix: [u8; 4]) -> Self {
+        NonceSequence { prefix, counter: 0 }
+    }
+
+    pub fn next_nonce(&mut self) -> Result<Nonce, String> {
+        if self.counter == u64::MAX {
+            return Err("Nonce sequence exhausted, the key must be rotated".to_string());
+        }
+
+        let mut nonce = [0u8; 12];
+        nonce[..4].copy_from_slice(&self.prefix);
+        nonce[4..].copy_from_slice(&self.counter.to_le_bytes());
+        self.counter += 1;
+
+        Ok(nonce)
+    }
+}
+
+#[derive(Clone)]
+pub struct ChaCha20Poly1305 {
+    key: [u8; 32],
+}
+
+impl ChaCha20Poly1305 {
+    pub fn new(key: [u8; 32]) -> Self {
+        ChaCha20Poly1305 { key }
+    }
+
+    fn tag(&self, nonce: &Nonce, aad: &[u8], ciphertext: &[u8]) -> [u8; 16] {
+        let mut one_time_key = [0u8; 32];
+        one_time_key.copy_from_slice(&chacha20_block(&self.key, 0, nonce)[..32]);
+
+        let mut message = Vec::with_capacity(aad.len() + ciphertext.len() + 32);
+        for data in [aad, ciphertext] {
+            message.extend_from_slice(data);
+            message.resize(message.len().next_multiple_of(16), 0);
+        }
+        message.extend_from_slice(&(aad.len() as u64).to_le_bytes());
+        message.extend_from_slice(&(ciphertext.len() as u64).to_le_bytes());
+
+        poly1305(&one_time_key, &message)
+    }
+}
+
+impl Aead for ChaCha20Poly1305 {
+    const KEY_LEN: usize = 32;
+    const TAG_LEN: usize = 16;
+
+    fn seal(&self, nonce: &Nonce, aad: &[u8], plaintext: &[u8]) -> Vec<u8> {
+        let mut sealed = chacha20(&self.key, 1, nonce, plaintext);
+        let tag = self.tag(nonce, aad, &sealed);
+        sealed.extend_from_slice(&tag);
+        sealed
+    }
+
+    fn open(&self, nonce: &Nonce, aad: &[u8], ciphertext: &[u8]) -> Result<Vec<u8>, String> {
+        if ciphertext.len() < Self::TAG_LEN {
+            return Err(format!(
+                "Ciphertext of {} bytes is shorter than the tag",
+                ciphertext.len()
+            ));
+        }
+
+        let (ciphertext, tag) = ciphertext.split_at(ciphertext.len() - Self::TAG_LEN);
+        let expected = self.tag(nonce, aad, ciphertext);
+        let difference = expected
+            .iter()
+            .zip(tag)
+            .fold(0, |acc, (a, b)| acc | (a ^ b));
+        if difference != 0 {
+            return Err("Authentication tag mismatch".to_string());
+        }
+
+        Ok(chacha20(&self.key, 1, nonce, ciphertext))
+    }
+}
+
+fn quarter_round(state: &mut [u32; 16], a: usize, b: usize, c: usize, d: usize) {
+    state[a] = state[a].wrapping_add(state[b]);
+    state[d] = (state[d] ^ state[a]).rotate_left(16);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_left(12);
+    state[a] = state[a].wrapping_add(state[b]);
+    state[d] = (state[d] ^ state[a]).rotate_left(8);
+    state[c] = state[c].wrapping_add(state[d]);
+    state[b] = (state[b] ^ state[c]).rotate_left(7);
+}
+
+fn chacha20_block(key: &[u8; 32], counter: u32, nonce: &Nonce) -> [u8; 64] {
+    let mut initial = [0u32; 16];
+    initial[..4].copy_from_slice(&[0x61707865, 0x3320646e, 0x79622d32, 0x6b206574]);
+    for (word, chunk) in initial[4..12].iter_mut().zip(key.chunks_exact(4)) {
+        *word = u32::from_le_bytes(chunk.try_into().unwrap());
+    }
+    initial[12] = counter;
+    for (word, chunk) in initial[13..].iter_mut().zip(nonce.chunks_exact(4)) {
+        *word = u32::from_le_bytes(chunk.try_into().unwrap());
+    }
+
+    let mut state = initial;
+    for _ in 0..10 {
+        quarter_round(&mut state, 0, 4, 8, 12);
+        quarter_round(&mut state, 1, 5, 9, 13);
+        quarter_round(&mut state, 2, 6, 10, 14);
+        quarter_round(&mut state, 3, 7, 11, 15);
+        quarter_round(&mut state, 0, 5, 10, 15);
+        quarter_round(&mut state, 1, 6, 11, 12);
+        quarter_round(&mut state, 2, 7, 8, 13);
+        quarter_round(&mut state, 3, 4, 9, 14);
+    }
+
+    let mut block = [0u8; 64];
+    for (i, chunk) in block.chunks_exact_mut(4).enumerate() {
+        chunk.copy_from_slice(&state[i].wrapping_add(initial[i]).to_le_bytes());
+    }
+
+    block
+}
+
+fn chacha20(key: &[u8; 32], counter: u32, nonce: &Nonce, data: &[u8]) -> Vec<u8> {
+    data.chunks(64)
+        .zip(counter..)
+        .flat_map(|(chunk, counter)| {
+            let keystream = chacha20_block(key, counter, nonce);
+            chunk
+                .iter()
+                .zip(keystream)
+                .map(|(byte, key)| byte ^ key)
+                .collect::<Vec<_>>()
+        })
+        .collect()
+}
+
+fn from_le_bytes(bytes: &[u8]) -> U256 {
+    let mut be = bytes.to_vec();
+    be.reverse();
+    <U256 as FieldUint>::from_be_bytes(&be).unwrap()
+}
+
+/// Poly1305 evaluated with U256 arithmetic modulo 2^130 - 5.
+fn poly1305(key: &[u8; 32], message: &[u8]) -> [u8; 16] {
+    let prime = (U256::ONE << 130) - U256::from(5);
+    let mut clamped = [0u8; 16];
+    clamped.copy_from_slice(&key[..16]);
+    for i in [3, 7, 11, 15] {
+        clamped[i] &= 0x0f;
+    }
+    for i in [4, 8, 12] {
+        clamped[i] &= 0xfc;
+    }
+    let r = from_le_bytes(&clamped);
+    let s = from_le_bytes(&key[16..]);
+
+    let mut accumulator = U256::ZERO;
+    for chunk in message.chunks(16) {
+        let block = from_le_bytes(chunk) + (U256::ONE << (8 * chunk.len() as u32));
+        accumulator = accumulator.add_mod(block, prime).mul_mod(r, prime);
+    }
+
+    let limbs = (accumulator + s).limbs();
+    let mut tag = [0u8; 16];
+    tag[..8].copy_from_slice(&limbs[0].to_le_bytes());
+    tag[8..].copy_from_slice(&limbs[1].to_le_bytes());
+    tag
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SUNSCREEN: &[u8] = b"Ladies and Gentlemen of the class of '99: If I could offer you only one tip for the future, sunscreen would be it.";
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    // RFC 8439, section 2.4.2.
+    #[test]
+    fn chacha20_matches_rfc_vector() {
+        let key: [u8; 32] = core::array::from_fn(|i| i as u8);
+        let nonce: Nonce = hex("000000000000004a00000000").try_into().unwrap();
+        let expected = hex(concat!(
+            "6e2e359a2568f98041ba0728dd0d6981e97e7aec1d4360c20a27afccfd9fae0b",
+            "f91b65c5524733ab8f593dabcd62b3571639d624e65152ab8f530c359f0861d8",
+            "07ca0dbf500d6a6156a38e088a22b65e52bc514d16ccf806818ce91ab7793736",
+            "5af90bbf74a35be6b40b8eedf2785e42874d",
+        ));
+
+        assert_eq!(chacha20(&key, 1, &nonce, SUNSCREEN), expected);
+    }
+
+    // RFC 8439, section 2.5.2.
+    #[test]
+    fn poly1305_matches_rfc_vector() {
+        let key: [u8; 32] = hex("85d6be7857556d337f4452fe42d506a80103808afb0db2fd4abff6af4149f51b")
+            .try_into()
+            .unwrap();
+
+        assert_eq!(
+            poly1305(&key, b"Cryptographic Forum Research Group").to_vec(),
+            hex("a8061dc1305136c6c22b8baf0c0127a9")
+        );
+    }
+
+    #[test]
+    fn poly1305_reduces_accumulator_near_modulus() {
+        let mut key = [0u8; 32];
+        key[..16].fill(0xff);
+        let mut message = vec![0xff; 16];
+        message.extend([0xfe; 3]);
+
+        assert_eq!(
+            poly1305(&key, &message).to_vec(),
+            hex("42eda8e53cf4430f37f4e33e31f4836e")
+        );
+    }
+
+    // RFC 8439, section 2.8.2.
+    #[test]
+    fn chacha20_poly1305_matches_rfc_vector() {
+        let key: [u8; 32] = core::array::from_fn(|i| 0x80 + i as u8);
+        let nonce: Nonce = hex("070000004041424344454647").try_into().unwrap();
+        let aad = hex("50515253c0c1c2c3c4c5c6c7");
+        let expected = hex(concat!(
+            "d31a8d34648e60db7b86afbc53ef7ec2a4aded51296e08fea9e2b5a736ee62d6",
+            "3dbea45e8ca9671282fafb69da92728b1a71de0a9e060b2905d6a5b67ecd3b36",
+            "92ddbd7f2d778b8c9803aee328091b58fab324e4fad675945585808b4831d7bc",
+            "3ff4def08e4b7a9de576d26586cec64b6116",
+            "1ae10b594f09e26a7e902ecbd0600691",
+        ));
+
+        let aead = ChaCha20Poly1305::new(key);
+        let sealed = aead.seal(&nonce, &aad, SUNSCREEN);
+        assert_eq!(sealed, expected);
+        assert_eq!(aead.open(&nonce, &aad, &sealed).unwrap(), SUNSCREEN);
+    }
+
+    #[test]
+    fn empty_message_only_produces_tag() {
+        let aead = ChaCha20Poly1305::new([0; 32]);
+        let sealed = aead.seal(&[0; 12], b"", b"");
+        assert_eq!(sealed, hex("4eb972c9a8fb3a1b382bb4d36f5ffad1"));
+        assert_eq!(aead.open(&[0; 12], b"", &sealed).unwrap(), b"");
+    }
+
+    #[test]
+    fn open_rejects_tampering() {
+        let aead = ChaCha20Poly1305::new([7; 32]);
+        let nonce = [1; 12];
+        let sealed = aead.seal(&nonce, b"header", b"attack at dawn");
+
+        let mut flipped = sealed.clone();
+        flipped[0] ^= 1;
+        assert!(aead.open(&nonce, b"header", &flipped).is_err());
+        assert!(aead.open(&nonce, b"other", &sealed).is_err());
+        assert!(aead.open(&[2; 12], b"header", &sealed).is_err());
+        assert!(ChaCha20Poly1305::new([8; 32])
+            .open(&nonce, b"header", &sealed)
+            .is_err());
+        assert!(aead.open(&nonce, b"header", &sealed[..15]).is_err());
+    }
+
+    #[test]
+    fn nonce_sequence_never_repeats() {
+        let mut sequence = NonceSequence::new([9, 9, 9, 9]);
+        let first = sequence.next_nonce().unwrap();
+        let second = sequence.next_nonce().unwrap();
+        assert_eq!(first, [9, 9, 9, 9, 0, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(second, [9, 9, 9, 9, 1, 0, 0, 0, 0, 0, 0, 0]);
+
+        sequence.counter = u64::MAX - 1;
+        assert!(sequence.next_nonce().is_ok());
+        assert!(sequence.next_nonce().is_err());
+    }
+}
@@ -0,0 +1,309 @@
+use std::fmt::{Display, Formatter, Result as FmtResult};
+
+use crate::{
+    coordinate::{Coordinate, GraphPoint},
+    field_element::FieldElement,
+    point::Point,
+    pow::Pow,
+    u256::U256,
+};
+
+/// Largest prime for which [`Curve::count_points`] enumerates the field.
+const COUNT_POINTS_LIMIT: u64 = 1 << 16;
+
+/// Which of the two roots y and p - y to take for a given x. Compressed SEC
+/// encodings and BIP 340 keys store this instead of y.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Parity {
+    Even,
+    Odd,
+}
+
+/// Elliptic curve y^2 = x^3 + ax + b. Points keep a copy of the curve they
+/// were built on, so points of different curves cannot be added.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Curve<G: GraphPoint> {
+    a: G,
+    b: G,
+}
+
+impl<G: GraphPoint> Curve<G> {
+    pub fn new<T: Into<G>>(a: T, b: T) -> Self {
+        Curve {
+            a: a.into(),
+            b: b.into(),
+        }
+    }
+
+    pub(crate) const fn from_parameters(a: G, b: G) -> Self {
+        Curve { a, b }
+    }
+
+    pub fn a(&self) -> G {
+        self.a
+    }
+
+    pub fn b(&self) -> G {
+        self.b
+    }
+
+    /// Whether (x, y) satisfies the curve equation. The point at infinity,
+    /// with both coordinates infinite, is on every curve, and no point has
+    /// only one infinite coordinate.
+    pub fn contains(&self, x: Coordinate<G>, y: Coordinate<G>) -> bool {
+        if x.is_infinity() || y.is_infinity() {
+            return x.is_infinity() && y.is_infinity();
+        }
+
+        let (a, b) = (Coordinate::Value(self.a), Coordinate::Value(self.b));
+        y.pow(2) == x.pow(3) + x * a + b
+    }
+
+    pub fn point<T: Into<Coordinate<G>>>(&self, x: T, y: T) -> Result<Point<G>, String> {
+        Point::on_curve(x.into(), y.into(), *self)
+    }
+
+    pub fn infinity(&self) -> Point<G> {
+        Point::on_curve(Coordinate::Infinity, Coordinate::Infinity, *self).unwrap()
+    }
+}
+
+impl Curve<FieldElement> {
+    pub fn from_finite_field<N: Into<U256>>(a: N, b: N, prime: N) -> Result<Self, String> {
+        let prime = prime.into();
+        Ok(Curve {
+            a: FieldElement::new(a.into(), prime)?,
+            b: FieldElement::new(b.into(), prime)?,
+        })
+    }
+
+    pub fn prime(&self) -> U256 {
+        self.a.prime
+    }
+
+    /// Element of the field the curve is defined over.
+    pub fn element<N: Into<U256>>(&self, number: N) -> Result<FieldElement, String> {
+        let number = number.into();
+        if number >= self.prime() {
+            return Err(format!(
+                "Number {} not in field range 0 to {}",
+                number,
+                self.prime() - U256::ONE
+            ));
+        }
+
+        Ok(FieldElement::new_unchecked(number, self.prime()))
+    }
+
+    pub fn point_from_numbers<N: Into<U256>>(
+        &self,
+        x: N,
+        y: N,
+    ) -> Result<Point<FieldElement>, String> {
+        self.point(self.element(x)?, self.element(y)?)
+    }
+
+    /// Number of points on the curve, the point at infinity included. Every
+    /// x gives two points when x^3 + ax + b is a non-zero square, one when it
+    /// is zero and none otherwise, so this costs one Legendre symbol per
+    /// element and only tiny primes are accepted. Larger curves need
+    /// baby-step giant-step on random points or Schoof's algorithm.
+    pub fn count_points(&self) -> Result<u64, String> {
+        let prime = self
+            .prime()
+            .to_u64()
+            .filter(|&prime| prime != 2 && prime <= COUNT_POINTS_LIMIT)
+            .ok_or_else(|| format!("Cannot count points over F{}", self.prime()))?;
+
+        let affine: i64 = (0..prime)
+            .map(|x| {
+                let x = FieldElement::new_unchecked(U256::from_u64(x), self.prime());
+                1 + (x * x * x + self.a * x + self.b).legendre() as i64
+            })
+            .sum();
+
+        Ok(affine as u64 + 1)
+    }
+
+    /// The interval p + 1 - 2√p to p + 1 + 2√p that Hasse's theorem
+    /// guarantees contains the number of points.
+    pub fn hasse_bound(&self) -> Result<(u64, u64), String> {
+        let prime = self
+            .prime()
+            .to_u64()
+            .filter(|&prime| prime < 1 << 62)
+            .ok_or_else(|| format!("Cannot bound the number of points over F{}", self.prime()))?;
+        let width = (4 * prime).isqrt();
+
+        Ok((prime + 1 - width, prime + 1 + width))
+    }
+
+    /// The y with the given parity such that (x, y) is on the curve. Fails
+    /// if x^3 + ax + b is not a square, or if the only root is zero and an
+    /// odd one was asked for.
+    pub fn solve_y(&self, x: FieldElement, parity: Parity) -> Result<FieldElement, String> {
+        if x.prime != self.prime() {
+            return Err(format!(
+                "x = {} is not in the field of order {}",
+                x.number,
+                self.prime()
+            ));
+        }
+
+        let root = (x * x * x + self.a * x + self.b)
+            .sqrt()
+            .ok_or_else(|| format!("No point on the curve has x = {}", x.number))?;
+        let y = if root.number.is_odd() == (parity == Parity::Odd) {
+            root
+        } else {
+            -root
+        };
+
+        if y.number.is_odd() != (parity == Parity::Odd) {
+            return Err(format!("No point with x = {} has odd y", x.number));
+        }
+
+        Ok(y)
+    }
+}
+
+impl<G: GraphPoint> Display for Curve<G> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "y^2 = x^3 + {}x + {}", self.a, self.b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        named_curve::{NamedCurve, Secp256k1},
+        real_value::RealValue,
+    };
+
+    #[test]
+    fn contains_points_satisfying_equation() {
+        let curve = Curve::<RealValue>::new(5, 7);
+        assert!(curve.contains((-1).into(), (-1).into()));
+        assert!(!curve.contains((-1).into(), (-2).into()));
+        assert!(curve.contains(Coordinate::Infinity, Coordinate::Infinity));
+    }
+
+    #[test]
+    fn builds_points_over_finite_field() {
+        let curve = Curve::from_finite_field(0, 7, 223).unwrap();
+        let point = curve.point_from_numbers(47, 71).unwrap();
+
+        assert_eq!(point, Point::from_finite_field(47, 71, 0, 7, 223).unwrap());
+        assert_eq!(point.curve(), curve);
+        assert_eq!(21 * point, curve.infinity());
+        assert!(curve.point_from_numbers(47, 72).is_err());
+        assert!(curve.point_from_numbers(300, 71).is_err());
+    }
+
+    fn solves_y_for_every_x(prime: u32) {
+        let curve = Curve::from_finite_field(0, 7, prime).unwrap();
+        for x in 0..prime {
+            let x = curve.element(x).unwrap();
+            for parity in [Parity::Even, Parity::Odd] {
+                match curve.solve_y(x, parity) {
+                    Ok(y) => {
+                        assert!(curve.point(x, y).is_ok());
+                        assert_eq!(y.number.is_odd(), parity == Parity::Odd);
+                    }
+                    Err(_) => {
+                        let y_squared = x * x * x + 7;
+                        assert!(!y_squared.is_square() || y_squared.number == U256::ZERO);
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn solve_y_recovers_both_roots() {
+        // 223 = 3 (mod 4) and 17 = 1 (mod 4) take different square root paths.
+        solves_y_for_every_x(223);
+        solves_y_for_every_x(17);
+
+        let curve = Curve::from_finite_field(0, 7, 223).unwrap();
+        let x = curve.element(47).unwrap();
+        assert_eq!(
+            curve.solve_y(x, Parity::Odd).unwrap().number,
+            U256::from(71)
+        );
+        assert_eq!(
+            curve.solve_y(x, Parity::Even).unwrap().number,
+            U256::from(152)
+        );
+    }
+
+    #[test]
+    fn solve_y_rejects_invalid_x() {
+        let curve = Curve::from_finite_field(0, 7, 223).unwrap();
+        let other_field = FieldElement::new(1, 17).unwrap();
+        assert!(curve.solve_y(other_field, Parity::Even).is_err());
+
+        // (0, 0) is the only point with x = 0 on y^2 = x^3 + x.
+        let curve = Curve::from_finite_field(1, 0, 223).unwrap();
+        let zero = curve.element(0).unwrap();
+        assert!(curve.solve_y(zero, Parity::Even).is_ok());
+        assert!(curve.solve_y(zero, Parity::Odd).is_err());
+    }
+
+    #[test]
+    fn counts_points_on_small_curves() {
+        let cases = [
+            (0, 7, 223, 252),
+            (0, 7, 17, 18),
+            (216, 10, 223, 222),
+            (5, 7, 223, 196),
+        ];
+
+        for (a, b, prime, expected) in cases {
+            let curve = Curve::from_finite_field(a, b, prime).unwrap();
+            let count = curve.count_points().unwrap();
+            assert_eq!(count, expected);
+
+            let (low, high) = curve.hasse_bound().unwrap();
+            assert!((low..=high).contains(&count));
+        }
+        assert_eq!(
+            Curve::from_finite_field(0, 7, 223).unwrap().hasse_bound(),
+            Ok((195, 253))
+        );
+    }
+
+    #[test]
+    fn point_orders_divide_the_number_of_points() {
+        let curve = Curve::from_finite_field(0, 7, 223).unwrap();
+        let count = curve.count_points().unwrap();
+        for (x, y) in [(47, 71), (15, 86), (143, 98)] {
+            let order = curve.point_from_numbers(x, y).unwrap().order().unwrap();
+            assert!(count.is_multiple_of(order));
+        }
+    }
+
+    #[test]
+    fn refuses_to_count_over_large_or_even_fields() {
+        assert!(Curve::from_finite_field(0, 1, 2)
+            .unwrap()
+            .count_points()
+            .is_err());
+        let secp256k1 = Secp256k1::curve();
+        assert!(secp256k1.count_points().is_err());
+        assert!(secp256k1.hasse_bound().is_err());
+    }
+
+    #[test]
+    fn cannot_create_curve_over_invalid_field() {
+        assert!(Curve::from_finite_field(0, 7, 221).is_err());
+        assert!(Curve::from_finite_field(0, 300, 223).is_err());
+    }
+
+    #[test]
+    fn displays_equation() {
+        let curve = Curve::<RealValue>::new(5, 7);
+        assert_eq!(curve.to_string(), "y^2 = x^3 + 5x + 7");
+    }
+}
@@ -0,0 +1,44 @@
+//! Curve shapes other than the short Weierstrass y^2 = x^3 + ax + b of
+//! [`crate::curve::Curve`]. Each form has its own addition law over the same
+//! field elements and implements [`crate::algebra::Group`], so the shared
+//! ladder multiplies its points. Every form also maps to an equivalent short
+//! Weierstrass curve, which is how results are compared across families.
+
+use std::fmt::Debug;
+
+use crate::{algebra::Group, curve::Curve, field_element::FieldElement, point::Point};
+
+pub mod edwards;
+pub mod montgomery;
+
+pub trait CurveForm: Copy {
+    type Point: Group + PartialEq + Debug;
+
+    /// Whether (x, y) satisfies the curve equation.
+    fn contains(&self, x: FieldElement, y: FieldElement) -> bool;
+    fn point(&self, x: FieldElement, y: FieldElement) -> Result<Self::Point, String>;
+    fn identity(&self) -> Self::Point;
+
+    /// The short Weierstrass curve this one is birationally equivalent to.
+    fn weierstrass(&self) -> Curve<FieldElement>;
+
+    /// Image of `point` on [`CurveForm::weierstrass`]. The map is a group
+    /// homomorphism, so sums and multiples can be checked on either side.
+    fn to_weierstrass(&self, point: Self::Point) -> Point<FieldElement>;
+}
+
+/// Every point of a curve over a tiny prime field, identity first.
+#[cfg(test)]
+fn all_points<C: CurveForm>(curve: &C, prime: u32) -> Vec<C::Point> {
+    let mut points = vec![curve.identity()];
+    for x in 0..prime {
+        for y in 0..prime {
+            let x = FieldElement::new(x, prime).unwrap();
+            let y = FieldElement::new(y, prime).unwrap();
+            if curve.contains(x, y) {
+                points.push(curve.point(x, y).unwrap());
+            }
+        }
+    }
+    points
+}
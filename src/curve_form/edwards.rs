@@ -0,0 +1,335 @@
+//! Twisted Edwards curves ax^2 + y^2 = 1 + dx^2y^2, the shape of Ed25519.
+//! One formula adds any two points, doubling included, and the identity is
+//! the ordinary point (0, 1).
+
+use std::ops::{Add, Mul, Neg};
+
+use crate::{
+    algebra::{multiply, Group, Monoid},
+    curve::Curve,
+    field_element::FieldElement,
+    is_zero::IsZero,
+    point::Point,
+    u256::U256,
+};
+
+use super::{
+    montgomery::{MontgomeryCurve, MontgomeryPoint},
+    CurveForm,
+};
+
+/// Coordinates of the Ed25519 base point (RFC 8032, section 5.1).
+const ED25519_BASE_X: U256 = U256::from_limbs([
+    0xC9562D608F25D51A,
+    0x692CC7609525A7B2,
+    0xC0A4E231FDD6DC5C,
+    0x216936D3CD6E53FE,
+]);
+const ED25519_BASE_Y: U256 = U256::from_limbs([
+    0x6666666666666658,
+    0x6666666666666666,
+    0x6666666666666666,
+    0x6666666666666666,
+]);
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct EdwardsCurve {
+    a: FieldElement,
+    d: FieldElement,
+}
+
+impl EdwardsCurve {
+    /// The curve is singular when a = d or either of them is zero. The
+    /// addition law only has no exceptions when a is a square and d is not,
+    /// as for Ed25519; on other curves adding some pairs returns an error.
+    pub fn new<N: Into<U256>>(a: N, d: N, prime: N) -> Result<Self, String> {
+        let prime = prime.into();
+        let a = FieldElement::new(a.into(), prime)?;
+        let d = FieldElement::new(d.into(), prime)?;
+        if a.is_zero() || d.is_zero() || a == d {
+            return Err(format!(
+                "Edwards curve with a = {} and d = {} is singular",
+                a.number, d.number
+            ));
+        }
+
+        Ok(EdwardsCurve { a, d })
+    }
+
+    /// -x^2 + y^2 = 1 - (121665 / 121666)x^2y^2 over F_(2^255 - 19).
+    pub fn ed25519() -> Self {
+        let prime = MontgomeryCurve::curve25519().prime();
+        let element = |n: u32| FieldElement::new_unchecked(U256::from(n), prime);
+
+        EdwardsCurve {
+            a: -element(1),
+            d: -element(121665) / element(121666),
+        }
+    }
+
+    /// The generator of the prime-order subgroup, the same group as the
+    /// Curve25519 base point.
+    pub fn ed25519_base() -> EdwardsPoint {
+        let curve = Self::ed25519();
+        let element = |n| FieldElement::new_unchecked(n, curve.prime());
+        curve
+            .point(element(ED25519_BASE_X), element(ED25519_BASE_Y))
+            .unwrap()
+    }
+
+    pub fn a(&self) -> FieldElement {
+        self.a
+    }
+
+    pub fn d(&self) -> FieldElement {
+        self.d
+    }
+
+    pub fn prime(&self) -> U256 {
+        self.a.prime
+    }
+
+    /// The birationally equivalent Montgomery curve, with A = 2(a + d) /
+    /// (a - d) and B = 4 / (a - d).
+    pub fn to_montgomery(&self) -> MontgomeryCurve {
+        let difference = self.a - self.d;
+        MontgomeryCurve::from_parameters(
+            (self.a + self.d) * 2 / difference,
+            FieldElement::new_unchecked(U256::from(4), self.prime()) / difference,
+        )
+    }
+
+    /// (x, y) maps to ((1 + y) / (1 - y), (1 + y) / ((1 - y)x)). The two
+    /// points with x = 0 are handled apart: (0, 1) is the identity and (0, -1)
+    /// is the point (0, 0) of order two.
+    pub fn map_to_montgomery(&self, point: EdwardsPoint) -> MontgomeryPoint {
+        let montgomery = self.to_montgomery();
+        let (x, y) = (point.x, point.y);
+        if x.is_zero() {
+            return if (y + -1).is_zero() {
+                montgomery.identity()
+            } else {
+                montgomery.point(x, x).unwrap()
+            };
+        }
+
+        let u = (y + 1) / (y * -1 + 1);
+        montgomery.point(u, u / x).unwrap()
+    }
+}
+
+impl CurveForm for EdwardsCurve {
+    type Point = EdwardsPoint;
+
+    fn contains(&self, x: FieldElement, y: FieldElement) -> bool {
+        let (xx, yy) = (x * x, y * y);
+        self.a * xx + yy == self.d * xx * yy + 1
+    }
+
+    fn point(&self, x: FieldElement, y: FieldElement) -> Result<EdwardsPoint, String> {
+        if x.prime != self.prime() || y.prime != self.prime() || !self.contains(x, y) {
+            return Err(format!(
+                "({}, {}) is not on the Edwards curve",
+                x.number, y.number
+            ));
+        }
+
+        Ok(EdwardsPoint { x, y, curve: *self })
+    }
+
+    fn identity(&self) -> EdwardsPoint {
+        EdwardsPoint {
+            x: FieldElement::new_unchecked(U256::ZERO, self.prime()),
+            y: FieldElement::new_unchecked(U256::ONE, self.prime()),
+            curve: *self,
+        }
+    }
+
+    fn weierstrass(&self) -> Curve<FieldElement> {
+        self.to_montgomery().weierstrass()
+    }
+
+    fn to_weierstrass(&self, point: EdwardsPoint) -> Point<FieldElement> {
+        self.to_montgomery()
+            .to_weierstrass(self.map_to_montgomery(point))
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct EdwardsPoint {
+    x: FieldElement,
+    y: FieldElement,
+    curve: EdwardsCurve,
+}
+
+impl EdwardsPoint {
+    pub fn x(&self) -> FieldElement {
+        self.x
+    }
+
+    pub fn y(&self) -> FieldElement {
+        self.y
+    }
+
+    pub fn curve(&self) -> EdwardsCurve {
+        self.curve
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.x.is_zero() && (self.y + -1).is_zero()
+    }
+
+    /// The same point on the Weierstrass curve of its Edwards curve, for
+    /// comparing with [`Point`] arithmetic.
+    pub fn to_weierstrass(self) -> Point<FieldElement> {
+        self.curve.to_weierstrass(self)
+    }
+}
+
+impl Add for EdwardsPoint {
+    type Output = Result<Self, String>;
+
+    fn add(self, other: Self) -> Self::Output {
+        if self.curve != other.curve {
+            return Err("Points are not on the same Edwards curve".to_string());
+        }
+
+        // Only a square d lets either denominator vanish.
+        let (x1, y1, x2, y2) = (self.x, self.y, other.x, other.y);
+        let dxxyy = self.curve.d * x1 * x2 * y1 * y2;
+        let (x_denominator, y_denominator) = (dxxyy + 1, dxxyy * -1 + 1);
+        if x_denominator.is_zero() || y_denominator.is_zero() {
+            return Err("Edwards addition law is undefined for these points".to_string());
+        }
+
+        let x3 = (x1 * y2 + y1 * x2) / x_denominator;
+        let y3 = (y1 * y2 - self.curve.a * x1 * x2) / y_denominator;
+
+        Ok(EdwardsPoint {
+            x: x3,
+            y: y3,
+            ..self
+        })
+    }
+}
+
+impl Neg for EdwardsPoint {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        EdwardsPoint { x: -self.x, ..self }
+    }
+}
+
+impl Monoid for EdwardsPoint {
+    fn identity(&self) -> Self {
+        self.curve.identity()
+    }
+
+    fn combine(self, other: Self) -> Self {
+        (self + other).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl Group for EdwardsPoint {
+    fn inverse(self) -> Self {
+        -self
+    }
+}
+
+impl Mul<EdwardsPoint> for U256 {
+    type Output = EdwardsPoint;
+
+    fn mul(self, point: EdwardsPoint) -> Self::Output {
+        multiply(point, self.bits(), |i| self.bit(i))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        coordinate::Coordinate,
+        curve_form::{all_points, montgomery::CURVE25519_ORDER},
+    };
+
+    /// a = 1 is a square and d = 2 is not modulo 13, so the law is complete.
+    fn small_curve() -> EdwardsCurve {
+        EdwardsCurve::new(1, 2, 13).unwrap()
+    }
+
+    #[test]
+    fn rejects_singular_curves() {
+        assert!(EdwardsCurve::new(0, 2, 13).is_err());
+        assert!(EdwardsCurve::new(1, 0, 13).is_err());
+        assert!(EdwardsCurve::new(3, 3, 13).is_err());
+    }
+
+    #[test]
+    fn addition_follows_group_laws() {
+        let curve = small_curve();
+        let points = all_points(&curve, 13);
+        let identity = curve.identity();
+
+        for &p in &points {
+            assert_eq!((p + identity).unwrap(), p);
+            assert!((p + -p).unwrap().is_identity());
+            for &q in &points {
+                assert_eq!((p + q).unwrap(), (q + p).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn incomplete_addition_fails_instead_of_panicking() {
+        // d = 3 is a square modulo 13, so the law has exceptions.
+        let curve = EdwardsCurve::new(1, 3, 13).unwrap();
+        let element = |n: u32| FieldElement::new(n, 13).unwrap();
+        let p = curve.point(element(4), element(6)).unwrap();
+        assert!((p + p).is_err());
+    }
+
+    #[test]
+    fn weierstrass_map_is_a_homomorphism() {
+        let curve = small_curve();
+        let points = all_points(&curve, 13);
+
+        for &p in &points {
+            for &q in &points {
+                let sum = (p.to_weierstrass() + q.to_weierstrass()).unwrap();
+                assert_eq!((p + q).unwrap().to_weierstrass(), sum);
+            }
+        }
+    }
+
+    #[test]
+    fn ed25519_base_point_has_prime_order() {
+        let base = EdwardsCurve::ed25519_base();
+        assert!((CURVE25519_ORDER * base).is_identity());
+        assert!(!((CURVE25519_ORDER - U256::ONE) * base).is_identity());
+    }
+
+    #[test]
+    fn ed25519_corresponds_to_curve25519() {
+        let curve = EdwardsCurve::ed25519();
+        let base = EdwardsCurve::ed25519_base();
+
+        // The birational map lands on a twist of Curve25519 by a square, so
+        // A and the u-coordinates agree while v is scaled.
+        let montgomery = curve.to_montgomery();
+        assert_eq!(montgomery.a(), MontgomeryCurve::curve25519().a());
+        assert_eq!(
+            curve.map_to_montgomery(base).x(),
+            MontgomeryCurve::curve25519_base().x()
+        );
+
+        let k = U256::from_u64(0x1234_5678_9abc_def0);
+        let Coordinate::Value(u) = MontgomeryCurve::curve25519_base().x() else {
+            unreachable!()
+        };
+        assert_eq!(
+            curve.map_to_montgomery(k * base).x(),
+            Coordinate::Value(MontgomeryCurve::curve25519().ladder(k, u).unwrap())
+        );
+    }
+}
@@ -0,0 +1,374 @@
+//! Montgomery curves By^2 = x^3 + Ax^2 + x, the shape of Curve25519. Their
+//! selling point is the x-only [`MontgomeryCurve::ladder`], which X25519 is
+//! built on.
+
+use std::ops::{Add, Mul, Neg};
+
+use crate::{
+    algebra::{multiply, Group, Monoid},
+    coordinate::Coordinate,
+    curve::Curve,
+    field_element::FieldElement,
+    is_zero::IsZero,
+    point::Point,
+    u256::U256,
+};
+
+use super::CurveForm;
+
+/// 2^255 - 19.
+const CURVE25519_P: U256 = U256::from_limbs([
+    0xFFFFFFFFFFFFFFED,
+    0xFFFFFFFFFFFFFFFF,
+    0xFFFFFFFFFFFFFFFF,
+    0x7FFFFFFFFFFFFFFF,
+]);
+
+/// v-coordinate of the Curve25519 base point u = 9 (RFC 7748, section 4.1).
+const CURVE25519_V: U256 = U256::from_limbs([
+    0x29E9C5A27ECED3D9,
+    0x923D4D7E6D7C61B2,
+    0xE01EDD2C7748D14C,
+    0x20AE19A1B8A086B4,
+]);
+
+/// Order of the prime subgroup of Curve25519 and Ed25519, 2^252 +
+/// 27742317777372353535851937790883648493. The full groups have cofactor 8.
+pub const CURVE25519_ORDER: U256 = U256::from_limbs([
+    0x5812631A5CF5D3ED,
+    0x14DEF9DEA2F79CD6,
+    0x0000000000000000,
+    0x1000000000000000,
+]);
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct MontgomeryCurve {
+    a: FieldElement,
+    b: FieldElement,
+}
+
+impl MontgomeryCurve {
+    /// The curve is singular when B = 0 or A^2 = 4.
+    pub fn new<N: Into<U256>>(a: N, b: N, prime: N) -> Result<Self, String> {
+        let prime = prime.into();
+        let a = FieldElement::new(a.into(), prime)?;
+        let b = FieldElement::new(b.into(), prime)?;
+        if b.is_zero() || (a * a + -4).is_zero() {
+            return Err(format!(
+                "Montgomery curve with A = {} and B = {} is singular",
+                a.number, b.number
+            ));
+        }
+
+        Ok(MontgomeryCurve { a, b })
+    }
+
+    pub(crate) fn from_parameters(a: FieldElement, b: FieldElement) -> Self {
+        MontgomeryCurve { a, b }
+    }
+
+    /// y^2 = x^3 + 486662x^2 + x over F_(2^255 - 19).
+    pub fn curve25519() -> Self {
+        MontgomeryCurve::new(U256::from(486662), U256::ONE, CURVE25519_P).unwrap()
+    }
+
+    /// The point with u = 9 that generates the prime-order subgroup.
+    pub fn curve25519_base() -> MontgomeryPoint {
+        let curve = Self::curve25519();
+        curve
+            .point(curve.element(9), curve.element(CURVE25519_V))
+            .unwrap()
+    }
+
+    pub fn a(&self) -> FieldElement {
+        self.a
+    }
+
+    pub fn b(&self) -> FieldElement {
+        self.b
+    }
+
+    pub fn prime(&self) -> U256 {
+        self.a.prime
+    }
+
+    fn element<N: Into<U256>>(&self, number: N) -> FieldElement {
+        FieldElement::new_unchecked(number.into(), self.prime())
+    }
+
+    /// x-coordinate of k·P from the x-coordinate of P alone, with the
+    /// Montgomery ladder of RFC 7748: (x2 : z2) and (x3 : z3) hold the
+    /// multiples n·P and (n + 1)·P of the bits read so far, and their
+    /// difference is always P, so P must not be (0, 0). None when k·P is the
+    /// point at infinity.
+    pub fn ladder(&self, k: U256, x: FieldElement) -> Option<FieldElement> {
+        let a24 = (self.a + -2) / self.element(4);
+        let (mut x2, mut z2) = (self.element(1), self.element(0));
+        let (mut x3, mut z3) = (x, self.element(1));
+
+        for i in (0..k.bits()).rev() {
+            if k.bit(i) {
+                (x2, z2, x3, z3) = (x3, z3, x2, z2);
+            }
+
+            let (sum, difference) = (x2 + z2, x2 - z2);
+            let (sum_squared, difference_squared) = (sum * sum, difference * difference);
+            let e = sum_squared - difference_squared;
+            let cross_difference = (x3 - z3) * sum;
+            let cross_sum = (x3 + z3) * difference;
+
+            x3 = (cross_difference + cross_sum) * (cross_difference + cross_sum);
+            z3 = x * (cross_difference - cross_sum) * (cross_difference - cross_sum);
+            x2 = sum_squared * difference_squared;
+            z2 = e * (sum_squared + a24 * e);
+
+            if k.bit(i) {
+                (x2, z2, x3, z3) = (x3, z3, x2, z2);
+            }
+        }
+
+        (!z2.is_zero()).then(|| x2 / z2)
+    }
+}
+
+impl CurveForm for MontgomeryCurve {
+    type Point = MontgomeryPoint;
+
+    fn contains(&self, x: FieldElement, y: FieldElement) -> bool {
+        self.b * y * y == x * x * x + self.a * x * x + x
+    }
+
+    fn point(&self, x: FieldElement, y: FieldElement) -> Result<MontgomeryPoint, String> {
+        if x.prime != self.prime() || y.prime != self.prime() || !self.contains(x, y) {
+            return Err(format!(
+                "({}, {}) is not on the Montgomery curve",
+                x.number, y.number
+            ));
+        }
+
+        Ok(MontgomeryPoint {
+            x: Coordinate::Value(x),
+            y: Coordinate::Value(y),
+            curve: *self,
+        })
+    }
+
+    fn identity(&self) -> MontgomeryPoint {
+        MontgomeryPoint {
+            x: Coordinate::Infinity,
+            y: Coordinate::Infinity,
+            curve: *self,
+        }
+    }
+
+    /// x = B·u - A/3 and y = B·v turn the equation into v^2 = u^3 + au + b
+    /// with a = (3 - A^2) / (3B^2) and b = (2A^3 - 9A) / (27B^3).
+    fn weierstrass(&self) -> Curve<FieldElement> {
+        let (a, b) = (self.a, self.b);
+        Curve::new(
+            (a * a * -1 + 3) / (b * b * 3),
+            (a * a * a * 2 - a * 9) / (b * b * b * 27),
+        )
+    }
+
+    fn to_weierstrass(&self, point: MontgomeryPoint) -> Point<FieldElement> {
+        let curve = self.weierstrass();
+        match (point.x, point.y) {
+            (Coordinate::Value(x), Coordinate::Value(y)) => curve
+                .point(x / self.b + self.a / (self.b * 3), y / self.b)
+                .unwrap(),
+            _ => curve.infinity(),
+        }
+    }
+}
+
+/// Affine point of a [`MontgomeryCurve`], infinite in both coordinates for
+/// the identity like [`Point`].
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct MontgomeryPoint {
+    x: Coordinate<FieldElement>,
+    y: Coordinate<FieldElement>,
+    curve: MontgomeryCurve,
+}
+
+impl MontgomeryPoint {
+    pub fn x(&self) -> Coordinate<FieldElement> {
+        self.x
+    }
+
+    pub fn y(&self) -> Coordinate<FieldElement> {
+        self.y
+    }
+
+    pub fn curve(&self) -> MontgomeryCurve {
+        self.curve
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.x.is_infinity()
+    }
+}
+
+impl Add for MontgomeryPoint {
+    type Output = Result<Self, String>;
+
+    fn add(self, other: Self) -> Self::Output {
+        if self.curve != other.curve {
+            return Err("Points are not on the same Montgomery curve".to_string());
+        }
+
+        let (Coordinate::Value(x1), Coordinate::Value(y1)) = (self.x, self.y) else {
+            return Ok(other);
+        };
+        let (Coordinate::Value(x2), Coordinate::Value(y2)) = (other.x, other.y) else {
+            return Ok(self);
+        };
+        if x1 == x2 && y1 == -y2 {
+            return Ok(self.curve.identity());
+        }
+
+        let (a, b) = (self.curve.a, self.curve.b);
+        let slope = if x1 == x2 {
+            (x1 * x1 * 3 + a * x1 * 2 + 1) / (b * y1 * 2)
+        } else {
+            (y2 - y1) / (x2 - x1)
+        };
+        let x3 = b * slope * slope - a - x1 - x2;
+        let y3 = slope * (x1 - x3) - y1;
+
+        Ok(MontgomeryPoint {
+            x: Coordinate::Value(x3),
+            y: Coordinate::Value(y3),
+            ..self
+        })
+    }
+}
+
+impl Neg for MontgomeryPoint {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        MontgomeryPoint {
+            y: self.y * -1,
+            ..self
+        }
+    }
+}
+
+impl Monoid for MontgomeryPoint {
+    fn identity(&self) -> Self {
+        self.curve.identity()
+    }
+
+    fn combine(self, other: Self) -> Self {
+        (self + other).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl Group for MontgomeryPoint {
+    fn inverse(self) -> Self {
+        -self
+    }
+}
+
+impl Mul<MontgomeryPoint> for U256 {
+    type Output = MontgomeryPoint;
+
+    fn mul(self, point: MontgomeryPoint) -> Self::Output {
+        multiply(point, self.bits(), |i| self.bit(i))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::curve_form::all_points;
+
+    #[test]
+    fn rejects_singular_curves() {
+        assert!(MontgomeryCurve::new(3, 0, 31).is_err());
+        assert!(MontgomeryCurve::new(2, 1, 31).is_err());
+        assert!(MontgomeryCurve::new(29, 1, 31).is_err());
+        assert!(MontgomeryCurve::new(3, 2, 31).is_ok());
+    }
+
+    #[test]
+    fn weierstrass_map_is_a_homomorphism() {
+        let curve = MontgomeryCurve::new(3, 2, 31).unwrap();
+        let weierstrass = curve.weierstrass();
+        let points = all_points(&curve, 31);
+
+        for &p in &points {
+            assert_eq!(curve.to_weierstrass(p).curve(), weierstrass);
+            assert_eq!(curve.to_weierstrass(-p), -curve.to_weierstrass(p));
+            for &q in &points {
+                let sum = (curve.to_weierstrass(p) + curve.to_weierstrass(q)).unwrap();
+                assert_eq!(curve.to_weierstrass((p + q).unwrap()), sum);
+            }
+        }
+    }
+
+    #[test]
+    fn ladder_matches_affine_multiplication() {
+        let curve = MontgomeryCurve::new(3, 2, 31).unwrap();
+        for p in all_points(&curve, 31).into_iter().skip(1) {
+            let Coordinate::Value(x) = p.x() else {
+                unreachable!()
+            };
+            if x.is_zero() {
+                continue;
+            }
+            for k in 0..40 {
+                let expected = match (U256::from(k) * p).x() {
+                    Coordinate::Value(x) => Some(x),
+                    Coordinate::Infinity => None,
+                };
+                assert_eq!(curve.ladder(U256::from(k), x), expected, "{}·{:?}", k, p);
+            }
+        }
+    }
+
+    #[test]
+    fn curve25519_base_point_has_prime_order() {
+        let base = MontgomeryCurve::curve25519_base();
+        assert!((CURVE25519_ORDER * base).is_identity());
+        assert!(!((CURVE25519_ORDER - U256::ONE) * base).is_identity());
+    }
+
+    fn little_endian_bytes(hex: &str) -> [u8; 32] {
+        core::array::from_fn(|i| u8::from_str_radix(&hex[2 * i..2 * i + 2], 16).unwrap())
+    }
+
+    fn from_little_endian(mut bytes: [u8; 32]) -> U256 {
+        bytes.reverse();
+        U256::from_be_bytes(bytes)
+    }
+
+    // RFC 7748, section 5.2.
+    #[test]
+    fn ladder_matches_x25519_vector() {
+        let curve = MontgomeryCurve::curve25519();
+        let mut scalar =
+            little_endian_bytes("a546e36bf0527c9d3b16154b82465edd62144c0ac1fc5a18506a2244ba449ac4");
+        // Clamping clears the low three bits and bit 255, and sets bit 254.
+        scalar[0] &= 248;
+        scalar[31] = (scalar[31] & 127) | 64;
+        let mut u =
+            little_endian_bytes("e6db6867583030db3594c1a424b15f7c726624ec26b3353b10a903a6d0ab1c4c");
+        u[31] &= 127;
+
+        let shared = curve
+            .ladder(
+                from_little_endian(scalar),
+                curve.element(from_little_endian(u)),
+            )
+            .unwrap();
+        assert_eq!(
+            shared.number,
+            from_little_endian(little_endian_bytes(
+                "c3da55379de9c6908e94ea4df28d084f32eccf03491c71f754b4075577a28552"
+            ))
+        );
+    }
+}
@@ -0,0 +1,148 @@
+//! Output script descriptor checksums (BIP 380), usable on descriptor strings
+//! coming from Bitcoin Core without parsing the descriptor itself.
+
+const INPUT_CHARSET: &str = concat!(
+    "0123456789()[],'/*abcdefgh@:$%{}",
+    "IJKLMNOPQRSTUVWXYZ&+-.;<=>?!^_|~",
+    "ijklmnopqrstuvwxyzABCDEFGH`#\"\\ ",
+);
+const CHECKSUM_CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+const GENERATOR: [u64; 5] = [
+    0xf5dee51989,
+    0xa9fdca3312,
+    0x1bab10e32d,
+    0x3706b1677a,
+    0x644d626ffd,
+];
+const CHECKSUM_LENGTH: usize = 8;
+
+fn polymod(symbols: &[u64]) -> u64 {
+    let mut checksum = 1u64;
+    for &value in symbols {
+        let top = checksum >> 35;
+        checksum = ((checksum & 0x7ffffffff) << 5) ^ value;
+        for (i, generator) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                checksum ^= generator;
+            }
+        }
+    }
+
+    checksum
+}
+
+/// Maps each character to its position in the 32-symbol groups of
+/// INPUT_CHARSET, and every three characters to an extra symbol encoding
+/// which groups they came from.
+fn expand(descriptor: &str) -> Result<Vec<u64>, String> {
+    let mut symbols = Vec::new();
+    let mut groups = Vec::new();
+
+    for c in descriptor.chars() {
+        let position = INPUT_CHARSET
+            .find(c)
+            .ok_or_else(|| format!("Invalid character {:?} in descriptor", c))?
+            as u64;
+        symbols.push(position & 31);
+        groups.push(position >> 5);
+
+        if groups.len() == 3 {
+            symbols.push(groups[0] * 9 + groups[1] * 3 + groups[2]);
+            groups.clear();
+        }
+    }
+
+    match groups[..] {
+        [first] => symbols.push(first),
+        [first, second] => symbols.push(first * 3 + second),
+        _ => {}
+    }
+
+    Ok(symbols)
+}
+
+/// Computes the checksum of a descriptor without one, e.g.
+/// `raw(deadbeef)` -> `89f8spxm`.
+pub fn descsum(descriptor: &str) -> Result<String, String> {
+    let mut symbols = expand(descriptor)?;
+    symbols.extend([0; CHECKSUM_LENGTH]);
+    let checksum = polymod(&symbols) ^ 1;
+
+    Ok((0..CHECKSUM_LENGTH)
+        .map(|i| CHECKSUM_CHARSET[((checksum >> (5 * (7 - i))) & 31) as usize] as char)
+        .collect())
+}
+
+/// Appends `#<checksum>` to a descriptor without one.
+pub fn descsum_create(descriptor: &str) -> Result<String, String> {
+    Ok(format!("{}#{}", descriptor, descsum(descriptor)?))
+}
+
+/// Verifies a `descriptor#checksum` string.
+pub fn descsum_check(descriptor: &str) -> Result<(), String> {
+    let (body, checksum) = descriptor
+        .rsplit_once('#')
+        .ok_or_else(|| format!("Descriptor {} has no checksum", descriptor))?;
+
+    if checksum.len() != CHECKSUM_LENGTH {
+        return Err(format!(
+            "Checksum {} should be {} characters long",
+            checksum, CHECKSUM_LENGTH
+        ));
+    }
+
+    let mut symbols = expand(body)?;
+    for c in checksum.bytes() {
+        let position = CHECKSUM_CHARSET
+            .iter()
+            .position(|&x| x == c)
+            .ok_or_else(|| format!("Invalid character {:?} in checksum", c as char))?;
+        symbols.push(position as u64);
+    }
+
+    if polymod(&symbols) != 1 {
+        return Err(format!("Invalid checksum for descriptor {}", body));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MULTISIG: &str = "sh(multi(2,[00000000/111'/222]xprvA1RpRA33e1JQ7ifknakTFpgNXPmW2YvmhqLQYMmrj4xJXXWYpDPS3xz7iAxn8L39njGVyuoseXzU6rcxFLJ8HFsTjSyQbLYnMpCqE2VbFWc,xprv9uPDJpEQgRQfDcW7BkF7eTya6RPxXeJCqCJGHuCJ4GiRVLzkTXBAJMu2qaMWPrS7AANYqdq6vcBcBUdJCVVFceUvJFjaPdGZ2y9WACViL4L/0))";
+
+    #[test]
+    fn creates_known_checksums() {
+        assert_eq!(
+            descsum_create("raw(deadbeef)").unwrap(),
+            "raw(deadbeef)#89f8spxm"
+        );
+        assert_eq!(
+            descsum_create("addr(mkmZxiEcEd8ZqjQWVZuC6so5dFMKEFpN2j)").unwrap(),
+            "addr(mkmZxiEcEd8ZqjQWVZuC6so5dFMKEFpN2j)#02wpgw69"
+        );
+        assert_eq!(descsum(MULTISIG).unwrap(), "ggrsrxfy");
+    }
+
+    #[test]
+    fn accepts_valid_checksums() {
+        assert!(descsum_check("raw(deadbeef)#89f8spxm").is_ok());
+        assert!(descsum_check(&format!("{}#ggrsrxfy", MULTISIG)).is_ok());
+    }
+
+    #[test]
+    fn rejects_invalid_checksums() {
+        assert!(descsum_check("raw(deadbeef)").is_err());
+        assert!(descsum_check("raw(deadbeef)#89f8spx").is_err());
+        assert!(descsum_check("raw(deadbeef)#89f8spxn").is_err());
+        assert!(descsum_check("raw(deadbeef)#89f8spxb").is_err());
+        assert!(descsum_check("raw(deadbeee)#89f8spxm").is_err());
+    }
+
+    #[test]
+    fn rejects_characters_outside_of_charset() {
+        assert!(descsum_create("raw(deadbeef)\u{e9}").is_err());
+    }
+}
@@ -0,0 +1,77 @@
+//! Elliptic-curve Diffie-Hellman over secp256k1. Each side multiplies its
+//! own secret by the other side's public point, and both arrive at a·b·G.
+
+use crate::{crypto::sha256::sha256, is_zero::IsZero, s256_point::S256Point, scalar::Scalar};
+
+/// How the shared point is turned into bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SharedSecret {
+    /// The 33-byte compressed SEC encoding of the point.
+    Point,
+    /// SHA-256 of the compressed point, the default hash of libsecp256k1.
+    Sha256,
+}
+
+/// Computes `secret`·`peer` and encodes it. The point type already
+/// guarantees that `peer` is on the curve, and since the group has prime
+/// order a non-zero secret and a finite peer never produce infinity.
+pub fn ecdh(secret: Scalar, peer: &S256Point, output: SharedSecret) -> Result<Vec<u8>, String> {
+    if secret.is_zero() {
+        return Err("ECDH secret must not be zero".to_string());
+    }
+    if peer.is_infinity() {
+        return Err("ECDH peer point must not be the point at infinity".to_string());
+    }
+
+    let shared = (secret * *peer).sec(true);
+
+    Ok(match output {
+        SharedSecret::Point => shared,
+        SharedSecret::Sha256 => sha256(&shared).to_vec(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(bytes: &[u8]) -> String {
+        bytes.iter().map(|byte| format!("{:02x}", byte)).collect()
+    }
+
+    #[test]
+    fn both_sides_derive_the_same_secret() {
+        let alice = Scalar::from(0xdeadbeef);
+        let bob = Scalar::from(0x0badcafe);
+        let alice_public = alice * S256Point::G;
+        let bob_public = bob * S256Point::G;
+
+        for output in [SharedSecret::Point, SharedSecret::Sha256] {
+            assert_eq!(
+                ecdh(alice, &bob_public, output),
+                ecdh(bob, &alice_public, output)
+            );
+        }
+    }
+
+    #[test]
+    fn ecdh_matches_known_shared_secret() {
+        let bob_public = Scalar::from(0x0badcafe) * S256Point::G;
+        let alice = Scalar::from(0xdeadbeef);
+
+        assert_eq!(
+            hex(&ecdh(alice, &bob_public, SharedSecret::Point).unwrap()),
+            "03a42b2ae045fe943b0a6b6b77d491a26613c7e9ec488b70fbbd665a121cbee487"
+        );
+        assert_eq!(
+            hex(&ecdh(alice, &bob_public, SharedSecret::Sha256).unwrap()),
+            "a7f5dd7633cfc03f9a88ab0890aef8edf262fcdd859a42ed0f61025efab8d746"
+        );
+    }
+
+    #[test]
+    fn ecdh_rejects_degenerate_inputs() {
+        assert!(ecdh(Scalar::ZERO, &S256Point::G, SharedSecret::Point).is_err());
+        assert!(ecdh(Scalar::ONE, &S256Point::infinity(), SharedSecret::Point).is_err());
+    }
+}
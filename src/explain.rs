@@ -0,0 +1,101 @@
+//! "Show your work" mode. With the `explain` feature enabled, field and point
+//! operations describe their intermediate steps to a sink installed with
+//! `with_sink`; without it `explain!` expands to nothing.
+
+#[cfg(feature = "explain")]
+use std::{cell::RefCell, fmt::Arguments};
+
+#[cfg(feature = "explain")]
+type Sink = Box<dyn FnMut(&str)>;
+
+#[cfg(feature = "explain")]
+thread_local! {
+    static SINK: RefCell<Option<Sink>> = RefCell::new(None);
+}
+
+/// Runs `f` with every step of the arithmetic it performs on this thread sent
+/// to `sink`, one line per step. The previous sink is restored afterwards.
+#[cfg(feature = "explain")]
+pub fn with_sink<R>(sink: impl FnMut(&str) + 'static, f: impl FnOnce() -> R) -> R {
+    let previous = SINK.with(|current| current.replace(Some(Box::new(sink))));
+    let result = f();
+    SINK.with(|current| current.replace(previous));
+    result
+}
+
+#[cfg(feature = "explain")]
+pub(crate) fn emit(step: Arguments) {
+    // The sink is taken out while it runs, so arithmetic done by the sink
+    // itself is not reported back to it.
+    let Some(mut sink) = SINK.with(|current| current.borrow_mut().take()) else {
+        return;
+    };
+    sink(&step.to_string());
+    SINK.with(|current| current.replace(Some(sink)));
+}
+
+#[cfg(feature = "explain")]
+macro_rules! explain {
+    ($($arg:tt)*) => {
+        $crate::explain::emit(format_args!($($arg)*))
+    };
+}
+
+#[cfg(not(feature = "explain"))]
+macro_rules! explain {
+    ($($arg:tt)*) => {
+        ()
+    };
+}
+
+pub(crate) use explain;
+
+#[cfg(all(test, feature = "explain"))]
+mod tests {
+    use std::{cell::RefCell, rc::Rc};
+
+    use super::*;
+    use crate::{field_element::FieldElement, point::Point};
+
+    fn record<R>(f: impl FnOnce() -> R) -> (R, Vec<String>) {
+        let steps = Rc::new(RefCell::new(Vec::new()));
+        let sink = Rc::clone(&steps);
+        let result = with_sink(move |step| sink.borrow_mut().push(step.to_string()), f);
+        let steps = steps.borrow().clone();
+        (result, steps)
+    }
+
+    #[test]
+    fn reports_field_operations() {
+        let a = FieldElement::new(7, 13).unwrap();
+        let b = FieldElement::new(12, 13).unwrap();
+        let (_, steps) = record(|| a + b);
+        assert_eq!(steps, ["7 + 12 = 6 (mod 13)"]);
+
+        let (_, steps) = record(|| a / b);
+        assert_eq!(steps, ["7 / 12 = 7 * 12^-1 = 7 * 12 = 6 (mod 13)"]);
+    }
+
+    #[test]
+    fn reports_slope_of_point_addition() {
+        let p1 = Point::from_finite_field(170, 142, 0, 7, 223).unwrap();
+        let p2 = Point::from_finite_field(60, 139, 0, 7, 223).unwrap();
+        let (sum, steps) = record(|| (p1 + p2).unwrap());
+        assert_eq!(sum, Point::from_finite_field(220, 181, 0, 7, 223).unwrap());
+        assert!(
+            steps.contains(&"slope = (y2 - y1) / (x2 - x1) = FieldElement_221(223)".to_string())
+        );
+        assert!(steps.contains(&"x3 = slope^2 - x1 - x2 = FieldElement_220(223)".to_string()));
+        assert!(steps.contains(&"y3 = slope * (x1 - x3) - y1 = FieldElement_181(223)".to_string()));
+    }
+
+    #[test]
+    fn nothing_is_reported_outside_of_with_sink() {
+        let a = FieldElement::new(7, 13).unwrap();
+        let b = FieldElement::new(12, 13).unwrap();
+        let _ = a * b / a;
+
+        let (_, steps) = record(|| a + b);
+        assert_eq!(steps, ["7 + 12 = 6 (mod 13)"]);
+    }
+}
@@ -1,95 +1,396 @@
 use std::{
-    fmt::{Display, Formatter, Result as FmtResult},
-    ops::{Add, Div, Mul, Neg, Sub},
+    fmt::{Display, Formatter, LowerHex, Result as FmtResult, UpperHex},
+    iter::{Product, Sum},
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
 };
 
-use crate::{pow::Pow, is_zero::IsZero};
+use crate::{
+    algebra::{multiply, Monoid, Multiplicative},
+    explain::explain,
+    field_uint::FieldUint,
+    forward_ref::{forward_ref_binop, forward_ref_op_assign},
+    is_zero::IsZero,
+    pow::Pow,
+    try_ops::{TryAdd, TryDiv, TryMul, TrySub},
+    u256::U256,
+};
 
+/// Element of the prime field of order `prime`, generic over the integer
+/// type used to store it.
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
-pub struct FieldElement {
-    pub number: u32,
-    pub prime: u32,
+pub struct GenericFieldElement<U: FieldUint> {
+    pub number: U,
+    pub prime: U,
 }
 
-impl FieldElement {
-    pub fn new(number: u32, prime: u32) -> Result<Self, String> {
+pub type FieldElement = GenericFieldElement<U256>;
+
+impl<U: FieldUint> GenericFieldElement<U> {
+    /// Creates an element, checking that `prime` is prime with Miller-Rabin.
+    pub fn new<N: Into<U>>(number: N, prime: N) -> Result<Self, String> {
+        let number = number.into();
+        let prime = prime.into();
+
+        if !prime.is_prime() {
+            return Err(format!("{} is not a valid field prime", prime));
+        }
+
         if number >= prime {
             return Err(format!(
                 "Number {} not in field range 0 to {}",
                 number,
-                prime - 1
+                prime - U::ONE
             ));
         }
 
-        Ok(FieldElement { number, prime })
+        Ok(GenericFieldElement { number, prime })
     }
 
-    fn positive_pow(&self, power: i32) -> Self {
-        let mut number = 1;
-        for _ in 0..power {
-            number *= self.number;
-            number = number.rem_euclid(self.prime);
+    /// Creates an element without any validation. The caller must guarantee
+    /// that `prime` is prime and `number` is below it.
+    pub fn new_unchecked<N: Into<U>>(number: N, prime: N) -> Self {
+        GenericFieldElement {
+            number: number.into(),
+            prime: prime.into(),
         }
+    }
 
-        Self {
+    fn reduce_i32(value: i32, prime: U) -> U {
+        let magnitude = U::from(value.unsigned_abs()).rem_euclid(prime);
+        if value.is_negative() {
+            U::ZERO.sub_mod(magnitude, prime)
+        } else {
+            magnitude
+        }
+    }
+
+    pub(crate) fn check_same_field(&self, other: &Self) -> Result<(), String> {
+        if self.prime != other.prime {
+            return Err(format!(
+                "Elements {} and {} have different prime fields",
+                self, other
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn checked_inverse(&self) -> Result<Self, String> {
+        let number = self
+            .number
+            .inv_mod(self.prime)
+            .ok_or_else(|| format!("Element {} has no multiplicative inverse", self))?;
+
+        Ok(Self {
             number,
             prime: self.prime,
+        })
+    }
+
+    pub fn inverse(&self) -> Self {
+        self.checked_inverse().unwrap_or_else(|e| panic!("{}", e))
+    }
+
+    pub fn checked_add(self, other: Self) -> Result<Self, String> {
+        self.check_same_field(&other)?;
+
+        Ok(Self {
+            number: self.number.add_mod(other.number, self.prime),
+            prime: self.prime,
+        })
+    }
+
+    pub fn checked_sub(self, other: Self) -> Result<Self, String> {
+        self.check_same_field(&other)?;
+
+        Ok(Self {
+            number: self.number.sub_mod(other.number, self.prime),
+            prime: self.prime,
+        })
+    }
+
+    pub fn checked_mul(self, other: Self) -> Result<Self, String> {
+        self.check_same_field(&other)?;
+
+        Ok(Self {
+            number: self.number.mul_mod(other.number, self.prime),
+            prime: self.prime,
+        })
+    }
+
+    pub fn checked_div(self, other: Self) -> Result<Self, String> {
+        self.check_same_field(&other)?;
+        self.checked_mul(other.checked_inverse()?)
+    }
+
+    /// Legendre symbol computed with Euler's criterion: 1 if the element is a
+    /// non-zero square, -1 if it is not a square and 0 for zero.
+    pub fn legendre(&self) -> i32 {
+        if self.number.is_zero() {
+            return 0;
+        }
+
+        let exponent = (self.prime - U::ONE) >> 1;
+        if self.number.pow_mod(exponent, self.prime) == U::ONE {
+            1
+        } else {
+            -1
         }
     }
 
-    fn negative_pow(&self, power: i32) -> Self {
-        let equivalent_power = power.rem_euclid(self.prime as i32 - 1);
-        dbg!(equivalent_power);
-        return self.positive_pow(equivalent_power);
+    pub fn is_square(&self) -> bool {
+        self.legendre() != -1
     }
 
-    pub fn inverse(&self) -> Self {
-        self.pow(self.prime as i32 - 2)
+    /// One of the square roots, if any, found with Tonelli-Shanks so that it
+    /// works for every odd prime and not only those congruent to 3 mod 4.
+    pub fn sqrt(&self) -> Option<Self> {
+        if self.number.is_zero() || self.prime == U::from(2) {
+            return Some(*self);
+        }
+        if !self.is_square() {
+            return None;
+        }
+        if self.prime.bit(1) {
+            // p = 3 (mod 4), so self^((p + 1) / 4) squares to self.
+            return Some(self.pow_uint((self.prime >> 2).add_mod(U::ONE, self.prime)));
+        }
+
+        // p - 1 = q * 2^s with q odd.
+        let mut q = self.prime - U::ONE;
+        let mut s = 0;
+        while !q.bit(0) {
+            q = q >> 1;
+            s += 1;
+        }
+
+        let non_residue = (2u32..)
+            .map(|z| Self::new_unchecked(U::from(z), self.prime))
+            .find(|z| z.legendre() == -1)?;
+        let mut c = non_residue.pow_uint(q);
+        let mut t = self.pow_uint(q);
+        let mut root = self.pow_uint((q >> 1).add_mod(U::ONE, self.prime));
+
+        // Invariant: root^2 = self * t, and t has order dividing 2^(s-1).
+        while t.number != U::ONE {
+            let mut i = 0;
+            let mut t_power = t;
+            while t_power.number != U::ONE {
+                t_power = t_power * t_power;
+                i += 1;
+            }
+
+            let mut b = c;
+            for _ in 0..s - i - 1 {
+                b = b * b;
+            }
+            s = i;
+            c = b * b;
+            t *= c;
+            root *= b;
+        }
+
+        Some(root)
+    }
+
+    /// The number as 32 big-endian bytes, zero-padded on the left.
+    pub fn to_bytes32(self) -> [u8; 32] {
+        let bytes = self.number.to_be_bytes();
+        let mut padded = [0u8; 32];
+        padded[32 - bytes.len()..].copy_from_slice(&bytes);
+        padded
+    }
+
+    /// Raises self to an exponent of the full width of the backing integer,
+    /// e.g. p - 2 or (p + 1) / 4, by square-and-multiply.
+    pub fn pow_uint(&self, exponent: U) -> Self {
+        multiply(Multiplicative(*self), exponent.bits(), |i| exponent.bit(i)).0
+    }
+
+    /// Iterates over self, self^2, self^3, ... up to and including the first
+    /// power equal to one, so the iterator yields the cyclic subgroup
+    /// generated by self and its length is the order of self. Zero only
+    /// yields itself.
+    pub fn powers(&self) -> Powers<U> {
+        Powers {
+            base: *self,
+            next: Some(*self),
+        }
+    }
+
+    /// Smallest primitive root of the field of order `prime`. p - 1 is
+    /// factored by trial division up to 2^20, so this fails unless what is
+    /// left of p - 1 after that is one or a prime.
+    pub fn find_generator(prime: U) -> Result<Self, String> {
+        Self::new(U::ZERO, prime)?;
+
+        let order = prime - U::ONE;
+        let factors = prime_factors(order, TRIAL_DIVISION_LIMIT)
+            .ok_or_else(|| format!("Cannot factor {} by trial division", order))?;
+
+        (1u32..)
+            .map(U::from)
+            .take_while(|&candidate| candidate < prime)
+            .find(|&candidate| {
+                factors
+                    .iter()
+                    .all(|&factor| candidate.pow_mod(order.div_rem(factor).0, prime) != U::ONE)
+            })
+            .map(|number| Self { number, prime })
+            .ok_or_else(|| format!("{} has no primitive root", prime))
+    }
+
+    /// Parses a big-endian number of up to `U::BITS / 8` significant bytes,
+    /// which must be below `prime`.
+    pub fn from_bytes(bytes: &[u8], prime: U) -> Result<Self, String> {
+        let number = U::from_be_bytes(bytes)
+            .ok_or_else(|| format!("{} bytes do not fit in a field element", bytes.len()))?;
+        Self::new(number, prime)
+    }
+}
+
+/// Largest divisor [`GenericFieldElement::find_generator`] tries.
+const TRIAL_DIVISION_LIMIT: u32 = 1 << 20;
+
+/// Distinct prime factors of `n` by trial division with divisors up to
+/// `limit`. Returns None if the cofactor left after that is not prime.
+pub(crate) fn prime_factors<U: FieldUint>(mut n: U, limit: u32) -> Option<Vec<U>> {
+    let mut factors = Vec::new();
+
+    for divisor in (2..=limit).map(U::from) {
+        let (quotient, remainder) = n.div_rem(divisor);
+        if quotient < divisor {
+            break;
+        }
+
+        if remainder.is_zero() {
+            factors.push(divisor);
+            while n.div_rem(divisor).1.is_zero() {
+                n = n.div_rem(divisor).0;
+            }
+        }
+    }
+
+    if n > U::ONE {
+        if !n.is_prime() {
+            return None;
+        }
+        factors.push(n);
+    }
+
+    Some(factors)
+}
+
+/// Iterator returned by [`GenericFieldElement::powers`].
+pub struct Powers<U: FieldUint> {
+    base: GenericFieldElement<U>,
+    next: Option<GenericFieldElement<U>>,
+}
+
+impl<U: FieldUint> Iterator for Powers<U> {
+    type Item = GenericFieldElement<U>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next?;
+        self.next = if current.number == U::ONE || current.number.is_zero() {
+            None
+        } else {
+            Some(current * self.base)
+        };
+
+        Some(current)
     }
 }
 
-impl Display for FieldElement {
+impl<U: FieldUint> Display for GenericFieldElement<U> {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         write!(f, "FieldElement_{}({})", self.number, self.prime)
     }
 }
 
-impl Pow for FieldElement {
-    fn pow(&self, exp: i32) -> Self {
-        if exp.is_positive() {
-            self.positive_pow(exp)
-        } else {
-            self.negative_pow(exp)
-        }
+/// Hex formats the number only, so `{:064x}` prints the usual fixed-width
+/// representation of a 256-bit value.
+impl<U: FieldUint + LowerHex> LowerHex for GenericFieldElement<U> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        LowerHex::fmt(&self.number, f)
     }
 }
 
-impl Add for FieldElement {
-    type Output = Self;
+impl<U: FieldUint + UpperHex> UpperHex for GenericFieldElement<U> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        UpperHex::fmt(&self.number, f)
+    }
+}
 
-    fn add(self, other: Self) -> Self::Output {
-        if self.prime != other.prime {
-            panic!(
-                "Elements {} and {} have different prime fields",
-                self, other
-            );
+impl<U: FieldUint> Pow for GenericFieldElement<U> {
+    fn pow(&self, exp: i32) -> Self {
+        if exp == 0 {
+            return self.pow_uint(U::ZERO);
         }
 
-        let number = (self.number + other.number) % self.prime;
+        // Fermat's little theorem: a^(p-1) = 1, so exponents can be taken mod
+        // p-1. Reduce to 1..=p-1 rather than 0..p-1 so zero stays zero, and
+        // invert first for negative exponents so zero panics.
+        let base = if exp < 0 { self.inverse() } else { *self };
+        let order = self.prime - U::ONE;
+        let exponent = U::from(exp.unsigned_abs() - 1)
+            .rem_euclid(order)
+            .add_mod(U::ONE, self.prime);
+        base.pow_uint(exponent)
+    }
+}
 
-        Self {
-            number,
-            prime: self.prime,
+impl<U: FieldUint> Pow<U256> for GenericFieldElement<U> {
+    fn pow(&self, exp: U256) -> Self {
+        if exp.is_zero() {
+            return self.pow_uint(U::ZERO);
         }
+
+        // Reduce to 1..=p-1 rather than 0..p-1 so zero stays zero.
+        let order = <U256 as FieldUint>::from_be_bytes(&(self.prime - U::ONE).to_be_bytes())
+            .expect("backing integers are at most 256 bits wide");
+        let exponent = (exp - U256::ONE) % order + U256::ONE;
+        self.pow_uint(U::from_be_bytes(&exponent.to_be_bytes()).unwrap())
+    }
+}
+
+impl<U: FieldUint> Monoid for Multiplicative<GenericFieldElement<U>> {
+    fn identity(&self) -> Self {
+        Multiplicative(GenericFieldElement::new_unchecked(U::ONE, self.0.prime))
+    }
+
+    fn combine(self, other: Self) -> Self {
+        let prime = self.0.prime;
+        Multiplicative(GenericFieldElement::new_unchecked(
+            self.0.number.mul_mod(other.0.number, prime),
+            prime,
+        ))
     }
 }
 
-impl Add<i32> for FieldElement {
+impl<U: FieldUint> Add for GenericFieldElement<U> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        let result = self.checked_add(other).unwrap_or_else(|e| panic!("{}", e));
+        explain!(
+            "{} + {} = {} (mod {})",
+            self.number,
+            other.number,
+            result.number,
+            self.prime
+        );
+        result
+    }
+}
+
+impl<U: FieldUint> Add<i32> for GenericFieldElement<U> {
     type Output = Self;
 
     fn add(self, other: i32) -> Self::Output {
-        let number = (self.number as i32 + other).rem_euclid(self.prime as i32);
-        let number = number as u32;
+        let number = self
+            .number
+            .add_mod(Self::reduce_i32(other, self.prime), self.prime);
 
         Self {
             number,
@@ -98,32 +399,29 @@ impl Add<i32> for FieldElement {
     }
 }
 
-impl Mul for FieldElement {
+impl<U: FieldUint> Mul for GenericFieldElement<U> {
     type Output = Self;
 
     fn mul(self, other: Self) -> Self::Output {
-        if self.prime != other.prime {
-            panic!(
-                "Elements {} and {} have different prime fields",
-                self, other
-            );
-        }
-
-        let number = (self.number * other.number) % self.prime;
-
-        Self {
-            number,
-            prime: self.prime,
-        }
+        let result = self.checked_mul(other).unwrap_or_else(|e| panic!("{}", e));
+        explain!(
+            "{} * {} = {} (mod {})",
+            self.number,
+            other.number,
+            result.number,
+            self.prime
+        );
+        result
     }
 }
 
-impl Mul<i32> for FieldElement {
+impl<U: FieldUint> Mul<i32> for GenericFieldElement<U> {
     type Output = Self;
 
     fn mul(self, other: i32) -> Self::Output {
-        let number = (self.number as i32 * other).rem_euclid(self.prime as i32);
-        let number = number as u32;
+        let number = self
+            .number
+            .mul_mod(Self::reduce_i32(other, self.prime), self.prime);
 
         Self {
             number,
@@ -132,45 +430,266 @@ impl Mul<i32> for FieldElement {
     }
 }
 
-impl Neg for FieldElement {
+impl<U: FieldUint> Neg for GenericFieldElement<U> {
     type Output = Self;
 
     fn neg(self) -> Self::Output {
-        let opposite = self.number as i32 * -1;
-        let number = opposite.rem_euclid(self.prime as i32);
-
         Self {
-            number: number as u32,
+            number: U::ZERO.sub_mod(self.number, self.prime),
             prime: self.prime,
         }
     }
 }
 
-impl Sub for FieldElement {
+impl<U: FieldUint> Sub for GenericFieldElement<U> {
     type Output = Self;
 
     fn sub(self, other: Self) -> Self::Output {
-        self + (-other)
+        let result = self.checked_sub(other).unwrap_or_else(|e| panic!("{}", e));
+        explain!(
+            "{} - {} = {} (mod {})",
+            self.number,
+            other.number,
+            result.number,
+            self.prime
+        );
+        result
     }
 }
 
-impl Div for FieldElement {
+impl<U: FieldUint> Div for GenericFieldElement<U> {
     type Output = Self;
 
     fn div(self, other: Self) -> Self::Output {
-        self * other.inverse()
+        let inverse = self
+            .check_same_field(&other)
+            .and_then(|()| other.checked_inverse())
+            .unwrap_or_else(|e| panic!("{}", e));
+        let result = self.checked_mul(inverse).unwrap();
+        explain!(
+            "{} / {} = {} * {}^-1 = {} * {} = {} (mod {})",
+            self.number,
+            other.number,
+            self.number,
+            other.number,
+            self.number,
+            inverse.number,
+            result.number,
+            self.prime
+        );
+        result
+    }
+}
+
+forward_ref_binop!(impl<U: FieldUint> Add, add for GenericFieldElement<U>);
+forward_ref_binop!(impl<U: FieldUint> Sub, sub for GenericFieldElement<U>);
+forward_ref_binop!(impl<U: FieldUint> Mul, mul for GenericFieldElement<U>);
+forward_ref_binop!(impl<U: FieldUint> Div, div for GenericFieldElement<U>);
+
+impl<U: FieldUint> AddAssign for GenericFieldElement<U> {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl<U: FieldUint> AddAssign<i32> for GenericFieldElement<U> {
+    fn add_assign(&mut self, other: i32) {
+        *self = *self + other;
+    }
+}
+
+impl<U: FieldUint> SubAssign for GenericFieldElement<U> {
+    fn sub_assign(&mut self, other: Self) {
+        *self = *self - other;
+    }
+}
+
+impl<U: FieldUint> MulAssign for GenericFieldElement<U> {
+    fn mul_assign(&mut self, other: Self) {
+        *self = *self * other;
+    }
+}
+
+impl<U: FieldUint> MulAssign<i32> for GenericFieldElement<U> {
+    fn mul_assign(&mut self, other: i32) {
+        *self = *self * other;
+    }
+}
+
+impl<U: FieldUint> DivAssign for GenericFieldElement<U> {
+    fn div_assign(&mut self, other: Self) {
+        *self = *self / other;
+    }
+}
+
+forward_ref_op_assign!(impl<U: FieldUint> AddAssign, add_assign for GenericFieldElement<U>);
+forward_ref_op_assign!(impl<U: FieldUint> SubAssign, sub_assign for GenericFieldElement<U>);
+forward_ref_op_assign!(impl<U: FieldUint> MulAssign, mul_assign for GenericFieldElement<U>);
+forward_ref_op_assign!(impl<U: FieldUint> DivAssign, div_assign for GenericFieldElement<U>);
+
+/// Panics on an empty iterator, since there is no prime to build zero from,
+/// and on elements of different fields.
+impl<U: FieldUint> Sum for GenericFieldElement<U> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.reduce(|acc, x| acc + x)
+            .expect("Cannot sum an empty iterator of field elements")
+    }
+}
+
+impl<'a, U: FieldUint> Sum<&'a Self> for GenericFieldElement<U> {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
+    }
+}
+
+/// Panics on an empty iterator and on elements of different fields.
+impl<U: FieldUint> Product for GenericFieldElement<U> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.reduce(|acc, x| acc * x)
+            .expect("Cannot multiply an empty iterator of field elements")
+    }
+}
+
+impl<'a, U: FieldUint> Product<&'a Self> for GenericFieldElement<U> {
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().product()
+    }
+}
+
+impl<U: FieldUint> TryAdd for GenericFieldElement<U> {
+    type Output = Self;
+
+    fn try_add(self, other: Self) -> Result<Self, String> {
+        self.checked_add(other)
+    }
+}
+
+impl<U: FieldUint> TrySub for GenericFieldElement<U> {
+    type Output = Self;
+
+    fn try_sub(self, other: Self) -> Result<Self, String> {
+        self.checked_sub(other)
     }
 }
 
-impl IsZero for FieldElement {
+impl<U: FieldUint> TryMul for GenericFieldElement<U> {
+    type Output = Self;
+
+    fn try_mul(self, other: Self) -> Result<Self, String> {
+        self.checked_mul(other)
+    }
+}
+
+impl<U: FieldUint> TryDiv for GenericFieldElement<U> {
+    type Output = Self;
+
+    fn try_div(self, other: Self) -> Result<Self, String> {
+        self.checked_div(other)
+    }
+}
+
+impl<U: FieldUint> IsZero for GenericFieldElement<U> {
     fn is_zero(&self) -> bool {
-        self.number == 0
+        self.number.is_zero()
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::s256_field::P;
+
+    // Fermat's little theorem, a^(p-2) = a^-1, used as an independent oracle.
+    fn fermat_inverse(element: FieldElement) -> FieldElement {
+        let exponent = element.prime - U256::from(2);
+        FieldElement {
+            number: element.number.pow_mod(exponent, element.prime),
+            prime: element.prime,
+        }
+    }
+
+    #[test]
+    fn powers_of_element_form_its_cyclic_subgroup() {
+        let element = FieldElement::new(2, 7).unwrap();
+        let powers: Vec<U256> = element.powers().map(|x| x.number).collect();
+        assert_eq!(powers, vec![U256::from(2), U256::from(4), U256::from(1)]);
+
+        assert_eq!(FieldElement::new(0, 7).unwrap().powers().count(), 1);
+        assert_eq!(FieldElement::new(1, 7).unwrap().powers().count(), 1);
+    }
+
+    #[test]
+    fn order_of_every_element_divides_field_order() {
+        for x in 1u32..223 {
+            let element = GenericFieldElement::<u32>::new(x, 223).unwrap();
+            assert_eq!(222 % element.powers().count(), 0);
+        }
+    }
+
+    #[test]
+    fn finds_primitive_roots() {
+        for (prime, root) in [(2u32, 1u32), (7, 3), (23, 5), (223, 3), (4_294_967_291, 2)] {
+            let generator = GenericFieldElement::<u32>::find_generator(prime).unwrap();
+            assert_eq!(generator.number, root);
+        }
+
+        let generator = GenericFieldElement::<u32>::find_generator(223).unwrap();
+        assert_eq!(generator.powers().count(), 222);
+    }
+
+    #[test]
+    fn cannot_find_generator_of_invalid_field() {
+        assert!(FieldElement::find_generator(U256::ONE).is_err());
+        assert!(FieldElement::find_generator(U256::from(8)).is_err());
+    }
+
+    #[test]
+    fn find_generator_gives_up_on_unfactorable_orders() {
+        // p - 1 = 2 * 1099511627791 * 1099511628401, both factors above the
+        // trial division limit.
+        let prime = U256::from_u128(2417851640636633232984383);
+        assert!(FieldElement::find_generator(prime).is_err());
+
+        // secp256k1's p - 1 is small factors times one large prime.
+        let secp256k1 = FieldElement::find_generator(crate::s256_field::P).unwrap();
+        assert_eq!(secp256k1.number, U256::from(3));
+    }
+
+    #[test]
+    fn hex_formatting_prints_number() {
+        let element = FieldElement::new(171, 223).unwrap();
+        assert_eq!(format!("{:x}", element), "ab");
+        assert_eq!(format!("{:#06X}", element), "0x00AB");
+
+        let small = GenericFieldElement::<u32>::new(171u32, 223).unwrap();
+        assert_eq!(format!("{:x}", small), "ab");
+    }
+
+    #[test]
+    fn bytes_roundtrip() {
+        let element = FieldElement::new(U256::from_hex("deadbeef").unwrap(), P).unwrap();
+        let bytes = element.to_bytes32();
+        assert_eq!(bytes[28..], [0xde, 0xad, 0xbe, 0xef]);
+        assert!(bytes[..28].iter().all(|&b| b == 0));
+        assert_eq!(FieldElement::from_bytes(&bytes, P), Ok(element));
+        assert_eq!(FieldElement::from_bytes(&bytes[28..], P), Ok(element));
+
+        let small = GenericFieldElement::<u32>::new(47u32, 223).unwrap();
+        assert_eq!(small.to_bytes32()[31], 47);
+        assert_eq!(
+            GenericFieldElement::<u32>::from_bytes(&small.to_bytes32(), 223),
+            Ok(small)
+        );
+    }
+
+    #[test]
+    fn cannot_parse_bytes_outside_of_field() {
+        assert!(FieldElement::from_bytes(&[223], U256::from(223)).is_err());
+        assert!(FieldElement::from_bytes(&[1; 33], P).is_err());
+        assert!(
+            GenericFieldElement::<u32>::from_bytes(&[1, 0, 0, 0, 0], LARGEST_U32_PRIME).is_err()
+        );
+    }
 
     #[test]
     fn cannot_create_element_with_number_higher_than_prime() {
@@ -306,6 +825,24 @@ mod tests {
         assert_eq!(element.pow(3), expected);
     }
 
+    #[test]
+    fn zero_to_multiples_of_field_order_stays_zero() {
+        let zero = FieldElement::new(0, 3).unwrap();
+        for exponent in [1, 2, 4, 6] {
+            assert_eq!(zero.pow(exponent), zero);
+        }
+        assert_eq!(zero.pow(0).number, U256::ONE);
+        let two = FieldElement::new(2, 3).unwrap();
+        assert_eq!(two.pow(2).number, U256::ONE);
+        assert_eq!(two.pow(-3), two);
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_to_negative_power_panics() {
+        FieldElement::new(0, 3).unwrap().pow(-2);
+    }
+
     #[test]
     fn fermat_theorem() {
         let prime = 31;
@@ -316,7 +853,7 @@ mod tests {
 
         set_power.next();
         for elem in set_power {
-            assert_eq!(elem.number, 1);
+            assert_eq!(elem.number, U256::ONE);
         }
     }
 
@@ -346,10 +883,330 @@ mod tests {
         assert_eq!(result, expected);
     }
 
+    #[test]
+    fn can_raise_element_to_exponents_wider_than_i32() {
+        let element = FieldElement::new(U256::from(3), P).unwrap();
+
+        assert_eq!(element.pow_uint(P - U256::from(2)), element.inverse());
+        assert_eq!(element.pow_uint(P - U256::ONE).number, U256::ONE);
+        assert_eq!(element.pow_uint(U256::ZERO).number, U256::ONE);
+
+        let small = GenericFieldElement::<u64>::new(3u64, 4_294_967_311).unwrap();
+        assert_eq!(small.pow_uint(4_294_967_309), small.inverse());
+    }
+
+    #[test]
+    fn pow_accepts_u256_exponents() {
+        let element = FieldElement::new(U256::from(3), P).unwrap();
+        let exponent = (P + U256::ONE) >> 2;
+        assert_eq!(element.pow(exponent), element.pow_uint(exponent));
+        assert_eq!(element.pow(U256::MAX), element.pow_uint(U256::MAX));
+
+        let small = GenericFieldElement::<u32>::new(3u32, 223).unwrap();
+        let zero = GenericFieldElement::<u32>::new(0u32, 223).unwrap();
+        assert_eq!(small.pow(U256::from(222 * 5 + 7)), small.pow(7));
+        let reduced = (U256::MAX % U256::from(222)).to_u64().unwrap() as u32;
+        assert_eq!(small.pow(U256::MAX), small.pow_uint(reduced));
+        assert_eq!(zero.pow(U256::from(222)), zero);
+        assert_eq!(zero.pow(U256::ZERO).number, 1);
+    }
+
     #[test]
     fn can_raise_element_to_power_of_negative_exponent() {
         let element = FieldElement::new(7, 13).unwrap();
         let expected = FieldElement::new(8, 13).unwrap();
         assert_eq!(element.pow(-3), expected);
     }
+
+    #[test]
+    fn can_operate_on_256_bit_numbers() {
+        let prime =
+            U256::from_hex("fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f")
+                .unwrap();
+        let number =
+            U256::from_hex("e9bb466a287385820942dc06bc69f2658575062102fbcd4f357fbc5af71a1bfc")
+                .unwrap();
+        let element = FieldElement::new(number, prime).unwrap();
+        let expected_inverse =
+            U256::from_hex("abd01f06da522bfe5d3bacb2e74a714c0c885efc8a5ff7307deb710670a605eb")
+                .unwrap();
+
+        assert_eq!(element.inverse().number, expected_inverse);
+        assert_eq!((element * element.inverse()).number, U256::ONE);
+        assert_eq!((element - element).number, U256::ZERO);
+    }
+
+    #[test]
+    fn operations_with_negative_integers_wrap_around_the_prime() {
+        let element = FieldElement::new(3, 7).unwrap();
+        assert_eq!(element + -5, FieldElement::new(5, 7).unwrap());
+        assert_eq!(element * -1, FieldElement::new(4, 7).unwrap());
+    }
+
+    #[test]
+    fn legendre_symbol_identifies_squares() {
+        let prime = 7;
+        let symbols: Vec<i32> = (0..prime)
+            .map(|x| FieldElement::new(x, prime).unwrap().legendre())
+            .collect();
+        assert_eq!(symbols, vec![0, 1, 1, -1, 1, -1, -1]);
+    }
+
+    #[test]
+    fn zero_and_squares_are_squares() {
+        let prime = 223;
+        for x in 0..prime {
+            let element = FieldElement::new(x, prime).unwrap();
+            assert!(element.pow(2).is_square());
+        }
+        assert!(!FieldElement::new(222, prime).unwrap().is_square());
+    }
+
+    #[test]
+    fn sqrt_finds_roots_for_primes_of_every_residue() {
+        // 17 = 1 (mod 16) exercises the Tonelli-Shanks loop, 223 = 3 (mod 4)
+        // needs no iterations.
+        for prime in [13, 17, 223] {
+            for x in 0..prime {
+                let element = FieldElement::new(x, prime).unwrap();
+                match element.sqrt() {
+                    Some(root) => assert_eq!(root * root, element),
+                    None => assert!(!element.is_square()),
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn inverse_matches_fermat_inverse() {
+        let prime = 223;
+        for x in 1..prime {
+            let element = FieldElement::new(x, prime).unwrap();
+            assert_eq!(element.inverse(), fermat_inverse(element));
+        }
+    }
+
+    #[test]
+    fn inverse_of_256_bit_element_matches_fermat_inverse() {
+        let prime =
+            U256::from_hex("fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f")
+                .unwrap();
+        let number =
+            U256::from_hex("25b2116aae6cff55ce0c3f08e12656f10e11160004524a7c3d2bd371fc80be13")
+                .unwrap();
+        let element = FieldElement::new(number, prime).unwrap();
+        assert_eq!(element.inverse(), fermat_inverse(element));
+    }
+
+    #[test]
+    #[should_panic]
+    fn cannot_invert_zero() {
+        let _ = FieldElement::new(0, 223).unwrap().inverse();
+    }
+
+    #[test]
+    fn cannot_create_field_with_prime_below_2() {
+        assert!(FieldElement::new(0, 0).is_err());
+        assert!(FieldElement::new(0, 1).is_err());
+    }
+
+    #[test]
+    fn cannot_create_field_with_composite_modulus() {
+        assert!(FieldElement::new(1, 4).is_err());
+        assert!(FieldElement::new(1, 561).is_err());
+        assert!(GenericFieldElement::<u64>::new(1u64, 4_294_967_291 * 4_294_967_279).is_err());
+    }
+
+    #[test]
+    fn unchecked_constructor_skips_validation() {
+        let element = FieldElement::new_unchecked(5, 4);
+        assert_eq!(element.number, U256::from(5));
+        assert_eq!(element.prime, U256::from(4));
+    }
+
+    const LARGEST_U32_PRIME: u32 = 4_294_967_291;
+
+    #[test]
+    fn can_multiply_elements_with_prime_near_u32_max() {
+        let element = FieldElement::new(LARGEST_U32_PRIME - 1, LARGEST_U32_PRIME).unwrap();
+        let expected = FieldElement::new(1, LARGEST_U32_PRIME).unwrap();
+        assert_eq!(element * element, expected);
+    }
+
+    #[test]
+    fn can_add_elements_with_prime_near_u32_max() {
+        let element = FieldElement::new(LARGEST_U32_PRIME - 1, LARGEST_U32_PRIME).unwrap();
+        let expected = FieldElement::new(LARGEST_U32_PRIME - 2, LARGEST_U32_PRIME).unwrap();
+        assert_eq!(element + element, expected);
+    }
+
+    #[test]
+    fn can_operate_with_integers_with_prime_near_u32_max() {
+        let element = FieldElement::new(LARGEST_U32_PRIME - 1, LARGEST_U32_PRIME).unwrap();
+        let expected =
+            FieldElement::new(LARGEST_U32_PRIME - 1 - 2_147_483_648, LARGEST_U32_PRIME).unwrap();
+        assert_eq!(element + i32::MIN, expected);
+        assert_eq!(
+            element * i32::MAX,
+            -FieldElement::new(2_147_483_647, LARGEST_U32_PRIME).unwrap()
+        );
+    }
+
+    #[test]
+    fn can_invert_elements_with_prime_near_u32_max() {
+        let element = FieldElement::new(123_456_789, LARGEST_U32_PRIME).unwrap();
+        let one = FieldElement::new(1, LARGEST_U32_PRIME).unwrap();
+        assert_eq!(element * element.inverse(), one);
+        assert_eq!(element.pow(-1), element.inverse());
+    }
+
+    #[test]
+    fn can_use_other_integer_backends() {
+        let prime = 18_446_744_073_709_551_557u64;
+        let element = GenericFieldElement::<u64>::new(prime - 1, prime).unwrap();
+        let one = GenericFieldElement::<u64>::new(1, prime).unwrap();
+        assert_eq!(element * element, one);
+        assert_eq!(element * element.inverse(), one);
+
+        let small = GenericFieldElement::<u32>::new(7u32, 19).unwrap();
+        assert_eq!(
+            small / small,
+            GenericFieldElement::<u32>::new(1u32, 19).unwrap()
+        );
+    }
+
+    #[test]
+    fn all_backends_agree() {
+        let prime = 223;
+        for x in 1..prime {
+            let wide = FieldElement::new(x, prime).unwrap();
+            let narrow = GenericFieldElement::<u32>::new(x, prime).unwrap();
+            let expected = (wide.pow(5) / wide + 3).number;
+            let result = (narrow.pow(5) / narrow + 3).number;
+            assert_eq!(U256::from(result), expected);
+        }
+    }
+
+    #[test]
+    fn checked_operations_fail_with_different_primes() {
+        let element1 = FieldElement::new(1, 2).unwrap();
+        let element2 = FieldElement::new(1, 3).unwrap();
+        assert!(element1.checked_add(element2).is_err());
+        assert!(element1.checked_sub(element2).is_err());
+        assert!(element1.checked_mul(element2).is_err());
+        assert!(element1.checked_div(element2).is_err());
+    }
+
+    #[test]
+    fn checked_operations_match_operators() {
+        let element1 = FieldElement::new(7, 19).unwrap();
+        let element2 = FieldElement::new(5, 19).unwrap();
+        assert_eq!(element1.checked_add(element2), Ok(element1 + element2));
+        assert_eq!(element1.checked_sub(element2), Ok(element1 - element2));
+        assert_eq!(element1.checked_mul(element2), Ok(element1 * element2));
+        assert_eq!(element1.checked_div(element2), Ok(element1 / element2));
+    }
+
+    #[test]
+    fn checked_division_by_zero_fails() {
+        let element = FieldElement::new(7, 19).unwrap();
+        let zero = FieldElement::new(0, 19).unwrap();
+        assert!(element.checked_div(zero).is_err());
+        assert!(zero.checked_inverse().is_err());
+    }
+
+    fn sum_of_products<T: TryAdd<Output = T> + TryMul<Output = T> + Copy>(
+        a: T,
+        b: T,
+        c: T,
+    ) -> Result<T, String> {
+        a.try_mul(b)?.try_add(c)
+    }
+
+    #[test]
+    fn try_operators_propagate_mismatched_fields() {
+        let element1 = FieldElement::new(2, 19).unwrap();
+        let element2 = FieldElement::new(3, 19).unwrap();
+        let other_field = FieldElement::new(3, 23).unwrap();
+        assert_eq!(
+            sum_of_products(element1, element2, element2),
+            Ok(FieldElement::new(9, 19).unwrap())
+        );
+        assert!(sum_of_products(element1, element2, other_field).is_err());
+    }
+
+    #[test]
+    fn assign_operators_match_binary_operators() {
+        let element1 = FieldElement::new(7, 19).unwrap();
+        let element2 = FieldElement::new(5, 19).unwrap();
+        let mut result = element1;
+
+        result += element2;
+        assert_eq!(result, element1 + element2);
+        result -= &element2;
+        assert_eq!(result, element1);
+        result *= element2;
+        assert_eq!(result, element1 * element2);
+        result /= &element2;
+        assert_eq!(result, element1);
+        result += 13;
+        result *= -1;
+        assert_eq!(result, (element1 + 13) * -1);
+    }
+
+    #[test]
+    fn can_operate_on_references() {
+        let element1 = FieldElement::new(7, 19).unwrap();
+        let element2 = FieldElement::new(5, 19).unwrap();
+        let (borrowed1, borrowed2) = (&element1, &element2);
+        assert_eq!(borrowed1 + borrowed2, element1 + element2);
+        assert_eq!(borrowed1 - element2, element1 - element2);
+        assert_eq!(element1 * borrowed2, element1 * element2);
+        assert_eq!(borrowed1 / borrowed2, element1 / element2);
+    }
+
+    #[test]
+    fn can_fold_over_borrowed_elements() {
+        let prime = 19;
+        let elements: Vec<FieldElement> = (1..prime)
+            .map(|x| FieldElement::new(x, prime).unwrap())
+            .collect();
+        let one = FieldElement::new(1, prime).unwrap();
+        let product = elements.iter().fold(one, |acc, x| acc * x);
+
+        // Wilson's theorem: (p-1)! = -1 mod p.
+        assert_eq!(product, -one);
+    }
+
+    #[test]
+    fn sum_and_product_match_fold() {
+        let prime = 19;
+        let elements: Vec<FieldElement> = (1..prime)
+            .map(|x| FieldElement::new(x, prime).unwrap())
+            .collect();
+        let one = FieldElement::new(1, prime).unwrap();
+
+        assert_eq!(elements.iter().product::<FieldElement>(), -one);
+        assert_eq!(elements.iter().sum::<FieldElement>().number, U256::ZERO);
+        assert_eq!(
+            elements.into_iter().take(3).sum::<FieldElement>(),
+            FieldElement::new(6, prime).unwrap()
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn cannot_sum_empty_iterator() {
+        Vec::<FieldElement>::new().into_iter().sum::<FieldElement>();
+    }
+
+    #[test]
+    #[should_panic]
+    fn cannot_sum_elements_of_different_fields() {
+        let elements = [
+            FieldElement::new(1, 3).unwrap(),
+            FieldElement::new(1, 5).unwrap(),
+        ];
+        elements.iter().sum::<FieldElement>();
+    }
 }
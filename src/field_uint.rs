@@ -0,0 +1,396 @@
+use std::{
+    fmt::{Debug, Display},
+    hash::Hash,
+    ops::{Shr, Sub},
+};
+
+use crate::{
+    algebra::{multiply, Monoid, Multiplicative},
+    u256::U256,
+};
+
+/// Unsigned integer that can back a field element. Operands of the `_mod`
+/// operations are expected to be already reduced modulo `modulus`.
+pub trait FieldUint:
+    Copy + Debug + Display + Eq + Ord + Hash + From<u32> + Sub<Output = Self> + Shr<u32, Output = Self>
+{
+    const ZERO: Self;
+    const ONE: Self;
+    const BITS: u32;
+
+    fn add_mod(self, other: Self, modulus: Self) -> Self;
+    fn sub_mod(self, other: Self, modulus: Self) -> Self;
+    fn mul_mod(self, other: Self, modulus: Self) -> Self;
+    fn div_rem(self, divisor: Self) -> (Self, Self);
+    fn bit(&self, index: u32) -> bool;
+    fn bits(&self) -> u32;
+
+    /// Big-endian bytes, always `BITS / 8` long.
+    fn to_be_bytes(self) -> Vec<u8>;
+
+    /// Parses big-endian bytes, left-padding shorter input. Returns None if
+    /// the value does not fit.
+    fn from_be_bytes(bytes: &[u8]) -> Option<Self>;
+
+    fn rem_euclid(self, modulus: Self) -> Self {
+        self.div_rem(modulus).1
+    }
+
+    fn is_zero(&self) -> bool {
+        *self == Self::ZERO
+    }
+
+    fn pow_mod(self, exponent: Self, modulus: Self) -> Self {
+        let base = Residue {
+            value: self.rem_euclid(modulus),
+            modulus,
+        };
+        multiply(Multiplicative(base), exponent.bits(), |i| exponent.bit(i))
+            .0
+            .value
+    }
+
+    /// Miller-Rabin test with the first 13 primes as bases. This is
+    /// deterministic below 3.3 * 10^24, which covers every u64 and most u128
+    /// values; above that a composite passing every base is astronomically
+    /// unlikely unless it was built on purpose to fool these exact bases.
+    fn is_prime(&self) -> bool {
+        const BASES: [u32; 13] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41];
+
+        let n = *self;
+        if n < Self::from(2) {
+            return false;
+        }
+
+        for base in BASES.map(Self::from) {
+            if n == base {
+                return true;
+            }
+            if n.rem_euclid(base).is_zero() {
+                return false;
+            }
+        }
+
+        let n_minus_one = n - Self::ONE;
+        let mut d = n_minus_one;
+        let mut s = 0;
+        while !d.bit(0) {
+            d = d >> 1;
+            s += 1;
+        }
+
+        BASES.map(Self::from).iter().all(|&base| {
+            let mut x = base.pow_mod(d, n);
+            if x == Self::ONE || x == n_minus_one {
+                return true;
+            }
+
+            for _ in 1..s {
+                x = x.mul_mod(x, n);
+                if x == n_minus_one {
+                    return true;
+                }
+            }
+
+            false
+        })
+    }
+
+    /// Modular inverse via the extended Euclidean algorithm. Bezout coefficients
+    /// are tracked modulo `modulus` so no signed arithmetic is needed. Returns
+    /// None when self and modulus are not coprime.
+    fn inv_mod(self, modulus: Self) -> Option<Self> {
+        let (mut r0, mut r1) = (modulus, self.rem_euclid(modulus));
+        let (mut t0, mut t1) = (Self::ZERO, Self::ONE.rem_euclid(modulus));
+
+        while !r1.is_zero() {
+            let (quotient, remainder) = r0.div_rem(r1);
+            let t2 = t0.sub_mod(quotient.mul_mod(t1, modulus), modulus);
+            (r0, r1) = (r1, remainder);
+            (t0, t1) = (t1, t2);
+        }
+
+        if r0 == Self::ONE {
+            Some(t0)
+        } else {
+            None
+        }
+    }
+}
+
+fn padded_be_bytes<const N: usize>(bytes: &[u8]) -> Option<[u8; N]> {
+    let start = bytes.iter().position(|&b| b != 0).unwrap_or(bytes.len());
+    let significant = &bytes[start..];
+    if significant.len() > N {
+        return None;
+    }
+
+    let mut padded = [0u8; N];
+    padded[N - significant.len()..].copy_from_slice(significant);
+    Some(padded)
+}
+
+macro_rules! impl_field_uint {
+    ($uint:ty, $wide:ty) => {
+        impl FieldUint for $uint {
+            const ZERO: Self = 0;
+            const ONE: Self = 1;
+            const BITS: u32 = <$uint>::BITS;
+
+            fn add_mod(self, other: Self, modulus: Self) -> Self {
+                ((self as $wide + other as $wide) % modulus as $wide) as $uint
+            }
+
+            fn sub_mod(self, other: Self, modulus: Self) -> Self {
+                if self >= other {
+                    self - other
+                } else {
+                    modulus - (other - self)
+                }
+            }
+
+            fn mul_mod(self, other: Self, modulus: Self) -> Self {
+                ((self as $wide * other as $wide) % modulus as $wide) as $uint
+            }
+
+            fn div_rem(self, divisor: Self) -> (Self, Self) {
+                (self / divisor, self % divisor)
+            }
+
+            fn bit(&self, index: u32) -> bool {
+                index < Self::BITS && (self >> index) & 1 == 1
+            }
+
+            fn bits(&self) -> u32 {
+                Self::BITS - self.leading_zeros()
+            }
+
+            fn to_be_bytes(self) -> Vec<u8> {
+                <$uint>::to_be_bytes(self).to_vec()
+            }
+
+            fn from_be_bytes(bytes: &[u8]) -> Option<Self> {
+                padded_be_bytes(bytes).map(<$uint>::from_be_bytes)
+            }
+        }
+    };
+}
+
+impl_field_uint!(u32, u64);
+impl_field_uint!(u64, u128);
+
+impl FieldUint for u128 {
+    const ZERO: Self = 0;
+    const ONE: Self = 1;
+    const BITS: u32 = u128::BITS;
+
+    fn add_mod(self, other: Self, modulus: Self) -> Self {
+        let (sum, carry) = self.overflowing_add(other);
+        if carry || sum >= modulus {
+            sum.wrapping_sub(modulus)
+        } else {
+            sum
+        }
+    }
+
+    fn sub_mod(self, other: Self, modulus: Self) -> Self {
+        if self >= other {
+            self - other
+        } else {
+            modulus - (other - self)
+        }
+    }
+
+    fn mul_mod(self, other: Self, modulus: Self) -> Self {
+        let product =
+            U256::from_u128(self).mul_mod(U256::from_u128(other), U256::from_u128(modulus));
+        let limbs = product.limbs();
+        (limbs[1] as u128) << 64 | limbs[0] as u128
+    }
+
+    fn div_rem(self, divisor: Self) -> (Self, Self) {
+        (self / divisor, self % divisor)
+    }
+
+    fn bit(&self, index: u32) -> bool {
+        index < Self::BITS && (self >> index) & 1 == 1
+    }
+
+    fn bits(&self) -> u32 {
+        Self::BITS - self.leading_zeros()
+    }
+
+    fn to_be_bytes(self) -> Vec<u8> {
+        u128::to_be_bytes(self).to_vec()
+    }
+
+    fn from_be_bytes(bytes: &[u8]) -> Option<Self> {
+        padded_be_bytes(bytes).map(u128::from_be_bytes)
+    }
+}
+
+impl FieldUint for U256 {
+    const ZERO: Self = U256::ZERO;
+    const ONE: Self = U256::ONE;
+    const BITS: u32 = 256;
+
+    fn add_mod(self, other: Self, modulus: Self) -> Self {
+        U256::add_mod(self, other, modulus)
+    }
+
+    fn sub_mod(self, other: Self, modulus: Self) -> Self {
+        U256::sub_mod(self, other, modulus)
+    }
+
+    fn mul_mod(self, other: Self, modulus: Self) -> Self {
+        U256::mul_mod(self, other, modulus)
+    }
+
+    fn div_rem(self, divisor: Self) -> (Self, Self) {
+        U256::div_rem(self, divisor)
+    }
+
+    fn bit(&self, index: u32) -> bool {
+        U256::bit(self, index)
+    }
+
+    fn bits(&self) -> u32 {
+        U256::bits(self)
+    }
+
+    fn to_be_bytes(self) -> Vec<u8> {
+        U256::to_be_bytes(self).to_vec()
+    }
+
+    fn from_be_bytes(bytes: &[u8]) -> Option<Self> {
+        padded_be_bytes(bytes).map(U256::from_be_bytes)
+    }
+}
+
+/// Integer modulo any modulus, prime or not, so that `pow_mod` can run on
+/// the shared ladder from [`crate::algebra`].
+#[derive(Clone, Copy)]
+struct Residue<U> {
+    value: U,
+    modulus: U,
+}
+
+impl<U: FieldUint> Monoid for Multiplicative<Residue<U>> {
+    fn identity(&self) -> Self {
+        Multiplicative(Residue {
+            value: U::ONE.rem_euclid(self.0.modulus),
+            modulus: self.0.modulus,
+        })
+    }
+
+    fn combine(self, other: Self) -> Self {
+        let modulus = self.0.modulus;
+        Multiplicative(Residue {
+            value: self.0.value.mul_mod(other.0.value, modulus),
+            modulus,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn inverses<U: FieldUint>(prime: u32) -> Vec<U> {
+        (1..prime)
+            .map(|x| U::from(x).inv_mod(U::from(prime)).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn backends_agree_on_inverses() {
+        let reference: Vec<U256> = inverses(223);
+        let as_u256 =
+            |values: Vec<u64>| -> Vec<U256> { values.into_iter().map(U256::from_u64).collect() };
+
+        assert_eq!(
+            as_u256(inverses::<u32>(223).into_iter().map(u64::from).collect()),
+            reference
+        );
+        assert_eq!(as_u256(inverses::<u64>(223)), reference);
+        assert_eq!(
+            as_u256(
+                inverses::<u128>(223)
+                    .into_iter()
+                    .map(|x| x as u64)
+                    .collect()
+            ),
+            reference
+        );
+    }
+
+    #[test]
+    fn backends_do_not_overflow_near_their_maximum() {
+        let p32 = u32::MAX - 4;
+        assert_eq!((p32 - 1).mul_mod(p32 - 1, p32), 1);
+        assert_eq!((p32 - 1).add_mod(p32 - 1, p32), p32 - 2);
+
+        let p64 = u64::MAX - 58;
+        assert_eq!((p64 - 1).mul_mod(p64 - 1, p64), 1);
+        assert_eq!((p64 - 1).add_mod(p64 - 1, p64), p64 - 2);
+
+        let p128 = u128::MAX - 158;
+        assert_eq!((p128 - 1).mul_mod(p128 - 1, p128), 1);
+        assert_eq!((p128 - 1).add_mod(p128 - 1, p128), p128 - 2);
+    }
+
+    #[test]
+    fn primality_test_matches_sieve() {
+        let mut sieve = vec![true; 10_000];
+        sieve[0] = false;
+        sieve[1] = false;
+        for i in 2..sieve.len() {
+            if sieve[i] {
+                for multiple in (i * i..sieve.len()).step_by(i) {
+                    sieve[multiple] = false;
+                }
+            }
+        }
+
+        for (n, &prime) in sieve.iter().enumerate() {
+            assert_eq!((n as u32).is_prime(), prime, "{}", n);
+        }
+    }
+
+    #[test]
+    fn primality_test_rejects_pseudoprimes() {
+        // Carmichael numbers and strong pseudoprimes to several small bases.
+        for n in [561u64, 1_105, 2_047, 1_373_653, 25_326_001, 3_215_031_751] {
+            assert!(!n.is_prime(), "{}", n);
+        }
+        assert!(!3_825_123_056_546_413_051u64.is_prime());
+        assert!(!(4_294_967_291u64 * 4_294_967_279).is_prime());
+    }
+
+    #[test]
+    fn primality_test_accepts_large_primes() {
+        assert!(u64::is_prime(&(u64::MAX - 58)));
+        assert!(u128::is_prime(&(u128::MAX - 158)));
+        assert!(
+            U256::from_hex("fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f")
+                .unwrap()
+                .is_prime()
+        );
+        assert!(!U256::MAX.is_prime());
+    }
+
+    #[test]
+    fn bytes_roundtrip() {
+        let value: u64 = 0x0102030405060708;
+        assert_eq!(FieldUint::to_be_bytes(value), vec![1, 2, 3, 4, 5, 6, 7, 8]);
+        assert_eq!(
+            <u64 as FieldUint>::from_be_bytes(&[0, 0, 1, 2]),
+            Some(0x0102)
+        );
+        assert_eq!(<u32 as FieldUint>::from_be_bytes(&[1, 2, 3, 4, 5]), None);
+        assert_eq!(
+            <u32 as FieldUint>::from_be_bytes(&[0, 1, 2, 3, 4]),
+            Some(0x01020304)
+        );
+    }
+}
@@ -0,0 +1,43 @@
+/// Implements `a op &b`, `&a op b` and `&a op &b` for a `Copy` type in terms
+/// of its by-value `a op b` implementation.
+macro_rules! forward_ref_binop {
+    (impl<$($generic:ident: $bound:path),*> $imp:ident, $method:ident for $t:ty) => {
+        impl<'a, $($generic: $bound),*> $imp<&'a $t> for $t {
+            type Output = <$t as $imp<$t>>::Output;
+
+            fn $method(self, other: &'a $t) -> Self::Output {
+                $imp::$method(self, *other)
+            }
+        }
+
+        impl<'a, $($generic: $bound),*> $imp<$t> for &'a $t {
+            type Output = <$t as $imp<$t>>::Output;
+
+            fn $method(self, other: $t) -> Self::Output {
+                $imp::$method(*self, other)
+            }
+        }
+
+        impl<'a, 'b, $($generic: $bound),*> $imp<&'a $t> for &'b $t {
+            type Output = <$t as $imp<$t>>::Output;
+
+            fn $method(self, other: &'a $t) -> Self::Output {
+                $imp::$method(*self, *other)
+            }
+        }
+    };
+}
+
+/// Implements `a op= &b` for a `Copy` type in terms of `a op= b`.
+macro_rules! forward_ref_op_assign {
+    (impl<$($generic:ident: $bound:path),*> $imp:ident, $method:ident for $t:ty) => {
+        impl<'a, $($generic: $bound),*> $imp<&'a $t> for $t {
+            fn $method(&mut self, other: &'a $t) {
+                $imp::$method(self, *other);
+            }
+        }
+    };
+}
+
+pub(crate) use forward_ref_binop;
+pub(crate) use forward_ref_op_assign;
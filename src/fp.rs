@@ -0,0 +1,360 @@
+use std::{
+    fmt::{Display, Formatter, LowerHex, Result as FmtResult, UpperHex},
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use crate::{
+    algebra::{multiply, Monoid, Multiplicative},
+    field_element::FieldElement,
+    field_uint::FieldUint,
+    is_zero::IsZero,
+    pow::Pow,
+    u256::U256,
+};
+
+/// Element of the prime field of order `P`, with the prime fixed at compile
+/// time. Elements of different fields are different types, so mixing them is
+/// a compile error instead of a panic.
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Hash)]
+pub struct Fp<const P: u64>(u64);
+
+impl<const P: u64> Fp<P> {
+    const VALID_PRIME: () = assert!(P >= 2, "field prime must be at least 2");
+    /// Evaluated once at compile time instead of on every construction.
+    const IS_PRIME: bool = is_prime(P);
+
+    pub fn new(number: u64) -> Result<Self, String> {
+        let () = Self::VALID_PRIME;
+
+        if !Self::IS_PRIME {
+            return Err(format!("{} is not a valid field prime", P));
+        }
+
+        if number >= P {
+            return Err(format!(
+                "Number {} not in field range 0 to {}",
+                number,
+                P - 1
+            ));
+        }
+
+        Ok(Fp(number))
+    }
+
+    pub fn number(&self) -> u64 {
+        self.0
+    }
+
+    pub fn pow_uint(&self, exponent: u64) -> Self {
+        multiply(
+            Multiplicative(*self),
+            u64::BITS - exponent.leading_zeros(),
+            |i| exponent & (1 << i) != 0,
+        )
+        .0
+    }
+
+    fn reduce_i32(value: i32, modulus: u64) -> u64 {
+        let magnitude = value.unsigned_abs() as u64 % modulus;
+        if value.is_negative() {
+            0.sub_mod(magnitude, modulus)
+        } else {
+            magnitude
+        }
+    }
+
+    pub fn inverse(&self) -> Self {
+        let number = self
+            .0
+            .inv_mod(P)
+            .unwrap_or_else(|| panic!("Element {} has no multiplicative inverse", self));
+
+        Fp(number)
+    }
+}
+
+const fn mul_mod(a: u64, b: u64, modulus: u64) -> u64 {
+    (a as u128 * b as u128 % modulus as u128) as u64
+}
+
+/// Const version of [`FieldUint::is_prime`]. Miller-Rabin with the first 13
+/// primes as bases is deterministic for every u64.
+const fn is_prime(n: u64) -> bool {
+    const BASES: [u64; 13] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41];
+
+    if n < 2 {
+        return false;
+    }
+
+    let mut i = 0;
+    while i < BASES.len() {
+        if n == BASES[i] {
+            return true;
+        }
+        if n.is_multiple_of(BASES[i]) {
+            return false;
+        }
+        i += 1;
+    }
+
+    let s = (n - 1).trailing_zeros();
+    let d = (n - 1) >> s;
+
+    let mut i = 0;
+    while i < BASES.len() {
+        let mut x = 1;
+        let mut base = BASES[i];
+        let mut exponent = d;
+        while exponent > 0 {
+            if exponent & 1 == 1 {
+                x = mul_mod(x, base, n);
+            }
+            base = mul_mod(base, base, n);
+            exponent >>= 1;
+        }
+
+        let mut round = 1;
+        while x != 1 && x != n - 1 && round < s {
+            x = mul_mod(x, x, n);
+            round += 1;
+        }
+        if x != 1 && x != n - 1 {
+            return false;
+        }
+        i += 1;
+    }
+
+    true
+}
+
+impl<const P: u64> From<Fp<P>> for FieldElement {
+    fn from(value: Fp<P>) -> Self {
+        FieldElement {
+            number: U256::from_u64(value.0),
+            prime: U256::from_u64(P),
+        }
+    }
+}
+
+impl<const P: u64> TryFrom<FieldElement> for Fp<P> {
+    type Error = String;
+
+    fn try_from(value: FieldElement) -> Result<Self, Self::Error> {
+        if value.prime != U256::from_u64(P) {
+            return Err(format!(
+                "{} is not an element of the field of order {}",
+                value, P
+            ));
+        }
+
+        Fp::new(value.number.to_u64().unwrap())
+    }
+}
+
+impl<const P: u64> Display for Fp<P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "Fp_{}({})", self.0, P)
+    }
+}
+
+impl<const P: u64> LowerHex for Fp<P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        LowerHex::fmt(&self.0, f)
+    }
+}
+
+impl<const P: u64> UpperHex for Fp<P> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        UpperHex::fmt(&self.0, f)
+    }
+}
+
+impl<const P: u64> Pow for Fp<P> {
+    fn pow(&self, exp: i32) -> Self {
+        if exp == 0 {
+            return self.pow_uint(0);
+        }
+
+        // Reduce to 1..=P-1 rather than 0..P-1 so zero stays zero, and invert
+        // first for negative exponents so zero panics.
+        let base = if exp < 0 { self.inverse() } else { *self };
+        base.pow_uint((exp.unsigned_abs() as u64 - 1) % (P - 1) + 1)
+    }
+}
+
+impl<const P: u64> Pow<U256> for Fp<P> {
+    fn pow(&self, exp: U256) -> Self {
+        if exp.is_zero() {
+            return self.pow_uint(0);
+        }
+
+        // Reduce to 1..=P-1 rather than 0..P-1 so zero stays zero.
+        let exponent = (exp - U256::ONE) % U256::from_u64(P - 1) + U256::ONE;
+        self.pow_uint(exponent.to_u64().unwrap())
+    }
+}
+
+impl<const P: u64> Monoid for Multiplicative<Fp<P>> {
+    fn identity(&self) -> Self {
+        Multiplicative(Fp(1 % P))
+    }
+
+    fn combine(self, other: Self) -> Self {
+        let (Fp(a), Fp(b)) = (self.0, other.0);
+        Multiplicative(Fp(a.mul_mod(b, P)))
+    }
+}
+
+impl<const P: u64> Add for Fp<P> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        Fp(self.0.add_mod(other.0, P))
+    }
+}
+
+impl<const P: u64> Add<i32> for Fp<P> {
+    type Output = Self;
+
+    fn add(self, other: i32) -> Self::Output {
+        Fp(self.0.add_mod(Self::reduce_i32(other, P), P))
+    }
+}
+
+impl<const P: u64> Sub for Fp<P> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        Fp(self.0.sub_mod(other.0, P))
+    }
+}
+
+impl<const P: u64> Mul for Fp<P> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        Fp(self.0.mul_mod(other.0, P))
+    }
+}
+
+impl<const P: u64> Mul<i32> for Fp<P> {
+    type Output = Self;
+
+    fn mul(self, other: i32) -> Self::Output {
+        Fp(self.0.mul_mod(Self::reduce_i32(other, P), P))
+    }
+}
+
+impl<const P: u64> Div for Fp<P> {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, other: Self) -> Self::Output {
+        self * other.inverse()
+    }
+}
+
+impl<const P: u64> Neg for Fp<P> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Fp(0.sub_mod(self.0, P))
+    }
+}
+
+impl<const P: u64> IsZero for Fp<P> {
+    fn is_zero(&self) -> bool {
+        self.0 == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{coordinate::Coordinate, point::Point};
+
+    type F223 = Fp<223>;
+
+    #[test]
+    fn cannot_create_element_outside_of_field() {
+        assert!(F223::new(223).is_err());
+    }
+
+    #[test]
+    fn cannot_create_element_of_composite_order() {
+        assert!(Fp::<221>::new(1).is_err());
+    }
+
+    #[test]
+    fn const_primality_test_matches_runtime_one() {
+        for n in (0..5000).chain([
+            3_825_123_056_546_413_051,
+            4_294_967_291 * 4_294_967_279,
+            u64::MAX - 58,
+            u64::MAX,
+        ]) {
+            assert_eq!(is_prime(n), n.is_prime(), "{}", n);
+        }
+    }
+
+    #[test]
+    fn arithmetic_matches_runtime_prime_field_element() {
+        for x in 1..223 {
+            let element = F223::new(x).unwrap();
+            let runtime = FieldElement::new(x as u32, 223).unwrap();
+            let result: FieldElement = (element.pow(-3) * element - element / (element * 2)).into();
+            assert_eq!(result, runtime.pow(-3) * runtime - runtime / (runtime * 2));
+        }
+    }
+
+    #[test]
+    fn zero_to_multiples_of_field_order_stays_zero() {
+        let zero = Fp::<3>::new(0).unwrap();
+        for exponent in [1, 2, 4] {
+            assert_eq!(zero.pow(exponent), zero);
+        }
+        assert_eq!(zero.pow(0).number(), 1);
+        assert_eq!(Fp::<2>::new(0).unwrap().pow(5).number(), 0);
+        assert_eq!(Fp::<2>::new(1).unwrap().pow(-5).number(), 1);
+        assert_eq!(
+            F223::new(47).unwrap().pow(-1),
+            F223::new(47).unwrap().inverse()
+        );
+    }
+
+    #[test]
+    fn pow_accepts_u256_exponents() {
+        let element = F223::new(47).unwrap();
+        let zero = F223::new(0).unwrap();
+        assert_eq!(element.pow(U256::from(222 * 3 + 5)), element.pow(5));
+        assert_eq!(element.pow(U256::ZERO), F223::new(1).unwrap());
+        assert_eq!(zero.pow(U256::from(222)), zero);
+    }
+
+    #[test]
+    fn converts_from_runtime_prime_field_element() {
+        let runtime = FieldElement::new(47, 223).unwrap();
+        assert_eq!(F223::try_from(runtime), Ok(F223::new(47).unwrap()));
+        assert!(Fp::<19>::try_from(runtime).is_err());
+    }
+
+    #[test]
+    fn can_be_used_as_point_coordinates() {
+        let point = Point::new(
+            F223::new(47).unwrap(),
+            F223::new(71).unwrap(),
+            F223::new(0).unwrap(),
+            F223::new(7).unwrap(),
+        )
+        .unwrap();
+        let infinity = Point::new(
+            Coordinate::Infinity,
+            Coordinate::Infinity,
+            F223::new(0).unwrap(),
+            F223::new(7).unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(21 * point, infinity);
+    }
+}
@@ -0,0 +1,284 @@
+//! Deterministic maps from byte strings to curve points whose discrete log
+//! nobody knows. [`try_and_increment`] is the textbook method and works on
+//! any curve, including the small ones from the book. [`hash_to_curve`]
+//! follows the secp256k1_XMD:SHA-256_SSWU_RO_ suite of RFC 9380.
+
+use crate::{
+    crypto::sha256::sha256,
+    curve::{Curve, Parity},
+    field_element::FieldElement,
+    is_zero::IsZero,
+    point::Point,
+    s256_field::{S256Field, P},
+    s256_point::S256Point,
+    u256::U256,
+};
+
+/// Hashes a 4-byte big-endian counter followed by `message` into a candidate
+/// x coordinate, incrementing the counter until x^3 + ax + b is a square.
+/// The y with even value is picked. About half of the candidates work, but
+/// the number of hashes depends on the message, so this leaks timing. The
+/// point is not multiplied by the cofactor; callers working in a subgroup
+/// have to do that themselves.
+pub fn try_and_increment(message: &[u8], curve: &Curve<FieldElement>) -> Point<FieldElement> {
+    let prime = curve.prime();
+    let mut counter: u32 = 0;
+
+    loop {
+        let data = [&counter.to_be_bytes(), message].concat();
+        let number = U256::from_be_bytes(sha256(&data)).div_rem(prime).1;
+        let x = FieldElement::new_unchecked(number, prime);
+
+        if let Ok(y) = curve.solve_y(x, Parity::Even) {
+            return curve.point(x, y).unwrap();
+        }
+
+        counter += 1;
+    }
+}
+
+/// RFC 9380 hash_to_curve for secp256k1: two field elements derived from
+/// `message` and the domain separation tag are each mapped with simplified
+/// SWU and the results added. Different protocols must use different tags.
+pub fn hash_to_curve(message: &[u8], dst: &[u8]) -> S256Point {
+    let [u0, u1] = hash_to_field(message, dst);
+    map_to_curve(u0) + map_to_curve(u1)
+}
+
+/// Simplified SWU needs a curve with a, b != 0, so it maps onto E': y^2 =
+/// x^3 + A'x + B', which is 3-isogenous to secp256k1.
+const ISO_A: S256Field = S256Field::from_reduced(U256::from_limbs([
+    0x405447C01A444533,
+    0xE953D363CB6F0E5D,
+    0xA08A5558F0F5D272,
+    0x3F8731ABDD661ADC,
+]));
+const ISO_B: S256Field = S256Field::from_reduced(U256::from_u64(1771));
+
+/// -11, the non-square RFC 9380 picks for this suite.
+const Z: S256Field = S256Field::from_reduced(U256::from_limbs([
+    0xFFFFFFFEFFFFFC24,
+    0xFFFFFFFFFFFFFFFF,
+    0xFFFFFFFFFFFFFFFF,
+    0xFFFFFFFFFFFFFFFF,
+]));
+
+/// x coordinate of the two points in the kernel of the isogeny, and the
+/// constants t = 6 * x0^2 + 2 * A' and u = 4 * y0^2 of Velu's formulas.
+const KERNEL_X: S256Field = S256Field::from_reduced(U256::from_limbs([
+    0x1D6AD4CE6AB9C5A5,
+    0xFC964A4DF050DF22,
+    0x041DA6957255EED5,
+    0x89291C84DE3E11F1,
+]));
+const VELU_T: S256Field = S256Field::from_reduced(U256::from_limbs([
+    0x4010DB260540D91D,
+    0xFB772A4728AFCFAC,
+    0xECE8777830312A16,
+    0x731B09EF2C479EF8,
+]));
+const VELU_U: S256Field = S256Field::from_reduced(U256::from_u64(28));
+
+/// 2^256 mod P, to fold the top bytes of a 48-byte number back into range.
+const TWO_POW_256: S256Field = S256Field::from_reduced(U256::from_u64(0x1000003D1));
+
+fn expand_message_xmd(message: &[u8], dst: &[u8], length: usize) -> Vec<u8> {
+    let dst = if dst.len() > 255 {
+        sha256(&[b"H2C-OVERSIZE-DST-", dst].concat()).to_vec()
+    } else {
+        dst.to_vec()
+    };
+    let dst_prime = [&dst[..], &[dst.len() as u8]].concat();
+    let blocks = length.div_ceil(32);
+    assert!(blocks <= 255, "Cannot expand a message to {} bytes", length);
+
+    let b0 = sha256(
+        &[
+            &[0u8; 64],
+            message,
+            &(length as u16).to_be_bytes(),
+            &[0],
+            &dst_prime,
+        ]
+        .concat(),
+    );
+
+    let mut output = Vec::with_capacity(blocks * 32);
+    let mut previous = [0u8; 32];
+    for i in 1..=blocks {
+        let mixed: Vec<u8> = b0.iter().zip(previous).map(|(a, b)| a ^ b).collect();
+        previous = sha256(&[&mixed[..], &[i as u8], &dst_prime].concat());
+        output.extend_from_slice(&previous);
+    }
+
+    output.truncate(length);
+    output
+}
+
+/// Each element takes 48 bytes, 128 bits more than P, so reducing them
+/// modulo P leaves a negligible bias.
+fn hash_to_field(message: &[u8], dst: &[u8]) -> [S256Field; 2] {
+    let uniform = expand_message_xmd(message, dst, 96);
+
+    core::array::from_fn(|i| {
+        let bytes = &uniform[48 * i..48 * (i + 1)];
+        let high = S256Field::from_bytes(&bytes[..16]).unwrap();
+        let low = U256::from_be_bytes(bytes[16..].try_into().unwrap())
+            .div_rem(P)
+            .1;
+        high * TWO_POW_256 + S256Field::from_reduced(low)
+    })
+}
+
+fn iso_curve_rhs(x: S256Field) -> S256Field {
+    x * x * x + ISO_A * x + ISO_B
+}
+
+/// Simplified SWU onto E'. Since Z is not a square, exactly one of the two
+/// candidate x coordinates has a square right-hand side.
+fn map_to_iso_curve(u: S256Field) -> (S256Field, S256Field) {
+    let u2 = u * u;
+    let tv1 = Z * Z * u2 * u2 + Z * u2;
+    let x1 = if tv1.is_zero() {
+        ISO_B / (Z * ISO_A)
+    } else {
+        -ISO_B / ISO_A * (tv1.inverse() + 1)
+    };
+
+    let (x, y) = match iso_curve_rhs(x1).sqrt() {
+        Some(y) => (x1, y),
+        None => {
+            let x2 = Z * u2 * x1;
+            (x2, iso_curve_rhs(x2).sqrt().unwrap())
+        }
+    };
+
+    if y.number().is_odd() == u.number().is_odd() {
+        (x, y)
+    } else {
+        (x, -y)
+    }
+}
+
+/// Velu's isogeny maps E' onto y^2 = x^3 + 7 * 3^6, which becomes secp256k1
+/// after dividing x by 3^2 and y by 3^3.
+fn isogeny(x: S256Field, y: S256Field) -> S256Point {
+    let d = x - KERNEL_X;
+    if d.is_zero() {
+        return S256Point::infinity();
+    }
+
+    let d2 = d * d;
+    let d3 = d2 * d;
+    let x = (x * d2 + VELU_T * d + VELU_U) / (d2 * 9);
+    let y = y * (d3 - VELU_T * d - VELU_U * 2) / (d3 * 27);
+
+    S256Point::new(x, y).unwrap()
+}
+
+fn map_to_curve(u: S256Field) -> S256Point {
+    let (x, y) = map_to_iso_curve(u);
+    isogeny(x, y)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DST: &[u8] = b"QUUX-V01-CS02-with-secp256k1_XMD:SHA-256_SSWU_RO_";
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn point(x: &str, y: &str) -> S256Point {
+        S256Point::new(
+            S256Field::from_hex(x).unwrap(),
+            S256Field::from_hex(y).unwrap(),
+        )
+        .unwrap()
+    }
+
+    // RFC 9380, appendix K.1.
+    #[test]
+    fn expand_message_xmd_matches_rfc_vectors() {
+        let dst = b"QUUX-V01-CS02-with-expander-SHA256-128";
+        assert_eq!(
+            expand_message_xmd(b"", dst, 32),
+            hex("68a985b87eb6b46952128911f2a4412bbc302a9d759667f87f7a21d803f07235")
+        );
+        assert_eq!(
+            expand_message_xmd(b"abc", dst, 32),
+            hex("d8ccab23b5985ccea865c6c97b6e5b8350e794e603b4b97902f53a8a0d605615")
+        );
+    }
+
+    // RFC 9380, appendix J.8.1.
+    #[test]
+    fn hash_to_curve_matches_rfc_vectors() {
+        let cases: [(&[u8], &str, &str); 3] = [
+            (
+                b"",
+                "c1cae290e291aee617ebaef1be6d73861479c48b841eaba9b7b5852ddfeb1346",
+                "64fa678e07ae116126f08b022a94af6de15985c996c3a91b64c406a960e51067",
+            ),
+            (
+                b"abc",
+                "3377e01eab42db296b512293120c6cee72b6ecf9f9205760bd9ff11fb3cb2c4b",
+                "7f95890f33efebd1044d382a01b1bee0900fb6116f94688d487c6c7b9c8371f6",
+            ),
+            (
+                b"abcdef0123456789",
+                "bac54083f293f1fe08e4a70137260aa90783a5cb84d3f35848b324d0674b0e3a",
+                "4436476085d4c3c4508b60fcf4389c40176adce756b398bdee27bca19758d828",
+            ),
+        ];
+
+        for (message, x, y) in cases {
+            assert_eq!(hash_to_curve(message, DST), point(x, y));
+        }
+    }
+
+    #[test]
+    fn map_to_curve_matches_rfc_intermediate_point() {
+        let [u0, _] = hash_to_field(b"", DST);
+        assert_eq!(
+            u0,
+            S256Field::from_hex("6b0f9910dd2ba71c78f2ee9f04d73b5f4c5f7fc773a701abea1e573cab002fb3")
+                .unwrap()
+        );
+        assert_eq!(
+            map_to_curve(u0),
+            point(
+                "74519ef88b32b425a095e4ebcc84d81b64e9e2c2675340a720bb1a1857b99f1e",
+                "c174fa322ab7c192e11748beed45b508e9fdb1ce046dee9c2cd3a2a86b410936",
+            )
+        );
+    }
+
+    #[test]
+    fn tag_separates_domains() {
+        assert_ne!(
+            hash_to_curve(b"abc", DST),
+            hash_to_curve(b"abc", b"another protocol")
+        );
+    }
+
+    #[test]
+    fn try_and_increment_lands_on_small_curve() {
+        let curve = Curve::from_finite_field(0, 7, 223).unwrap();
+        assert_eq!(
+            try_and_increment(b"Programming Bitcoin", &curve),
+            curve.point_from_numbers(201, 78).unwrap()
+        );
+
+        for message in [&b"a"[..], b"b", b"c", b"d"] {
+            let point = try_and_increment(message, &curve);
+            assert!(!point.x.is_infinity());
+            assert_eq!(point, try_and_increment(message, &curve));
+        }
+    }
+}
@@ -1,3 +1,3 @@
 pub trait IsZero {
     fn is_zero(&self) -> bool;
-}
\ No newline at end of file
+}
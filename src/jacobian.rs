@@ -0,0 +1,479 @@
+//! Jacobian coordinates: (X, Y, Z) stands for the affine point
+//! (X / Z^2, Y / Z^3), and Z = 0 for the point at infinity. Adding and
+//! doubling need no division, so a scalar multiplication only pays for one
+//! inversion when converting back to affine.
+
+use std::ops::Add;
+
+use crate::{
+    algebra::{Group, Monoid},
+    coordinate::{Coordinate, GraphPoint},
+    curve::Curve,
+    point::Point,
+    representation::Representation,
+    u256::U256,
+};
+
+#[derive(Debug, Clone, Copy)]
+pub struct JacobianPoint<G: GraphPoint> {
+    x: G,
+    y: G,
+    z: G,
+    curve: Curve<G>,
+}
+
+// GraphPoint has no constants, so zero and one are derived from any value of
+// the same field.
+#[allow(clippy::erasing_op)]
+fn zero_like<G: GraphPoint>(value: G) -> G {
+    value * 0
+}
+
+fn one_like<G: GraphPoint>(value: G) -> G {
+    zero_like(value) + 1
+}
+
+impl<G: GraphPoint> JacobianPoint<G> {
+    pub fn infinity(curve: Curve<G>) -> Self {
+        JacobianPoint {
+            x: one_like(curve.a()),
+            y: one_like(curve.a()),
+            z: zero_like(curve.a()),
+            curve,
+        }
+    }
+
+    pub fn is_infinity(&self) -> bool {
+        self.z.is_zero()
+    }
+
+    pub fn to_affine(self) -> Point<G> {
+        if self.is_infinity() {
+            return self.curve.infinity();
+        }
+
+        self.to_affine_with(one_like(self.z) / self.z)
+    }
+
+    /// Normalizes every point with a single field inversion by Montgomery's
+    /// trick. The output is in the same order as the input.
+    pub fn to_affine_batch(points: &[Self]) -> Vec<Point<G>> {
+        let z_values: Vec<G> = points
+            .iter()
+            .filter(|point| !point.is_infinity())
+            .map(|point| point.z)
+            .collect();
+        let mut z_inverses = batch_invert(&z_values).into_iter();
+
+        points
+            .iter()
+            .map(|point| {
+                if point.is_infinity() {
+                    point.curve.infinity()
+                } else {
+                    point.to_affine_with(z_inverses.next().unwrap())
+                }
+            })
+            .collect()
+    }
+
+    fn to_affine_with(self, z_inverse: G) -> Point<G> {
+        let z_inverse_squared = z_inverse * z_inverse;
+        Point::from_parts(
+            Coordinate::Value(self.x * z_inverse_squared),
+            Coordinate::Value(self.y * z_inverse_squared * z_inverse),
+            self.curve,
+        )
+    }
+
+    // dbl-1998-cmo-2 from the Explicit-Formulas Database, for any a.
+    pub fn double(self) -> Self {
+        if self.is_infinity() || self.y.is_zero() {
+            return Self::infinity(self.curve);
+        }
+
+        let xx = self.x * self.x;
+        let yy = self.y * self.y;
+        let zz = self.z * self.z;
+        let s = self.x * yy * 4;
+        let m = xx * 3 + self.curve.a() * zz * zz;
+        let x = m * m - s * 2;
+        let y = m * (s - x) - yy * yy * 8;
+        let z = self.y * self.z * 2;
+
+        JacobianPoint { x, y, z, ..self }
+    }
+
+    pub fn negate(self) -> Self {
+        JacobianPoint {
+            y: zero_like(self.y) - self.y,
+            ..self
+        }
+    }
+}
+
+impl<G: GraphPoint> Representation<G> for JacobianPoint<G> {
+    fn from_affine(point: Point<G>) -> Self {
+        point.into()
+    }
+
+    fn to_affine(self) -> Point<G> {
+        JacobianPoint::to_affine(self)
+    }
+}
+
+impl<G: GraphPoint> From<Point<G>> for JacobianPoint<G> {
+    fn from(point: Point<G>) -> Self {
+        match (point.x, point.y) {
+            (Coordinate::Value(x), Coordinate::Value(y)) => JacobianPoint {
+                x,
+                y,
+                z: one_like(x),
+                curve: point.curve(),
+            },
+            _ => Self::infinity(point.curve()),
+        }
+    }
+}
+
+impl<G: GraphPoint> Monoid for JacobianPoint<G> {
+    fn identity(&self) -> Self {
+        JacobianPoint::infinity(self.curve)
+    }
+
+    fn combine(self, other: Self) -> Self {
+        self + other
+    }
+
+    fn double(self) -> Self {
+        JacobianPoint::double(self)
+    }
+}
+
+impl<G: GraphPoint> Group for JacobianPoint<G> {
+    fn inverse(self) -> Self {
+        self.negate()
+    }
+}
+
+/// Both points must be on the same curve; callers are expected to have
+/// checked that on the affine points they came from.
+impl<G: GraphPoint> Add for JacobianPoint<G> {
+    type Output = Self;
+
+    // add-1998-cmo-2 from the Explicit-Formulas Database.
+    fn add(self, other: Self) -> Self::Output {
+        if self.is_infinity() {
+            return other;
+        }
+        if other.is_infinity() {
+            return self;
+        }
+
+        let z1z1 = self.z * self.z;
+        let z2z2 = other.z * other.z;
+        let u1 = self.x * z2z2;
+        let u2 = other.x * z1z1;
+        let s1 = self.y * other.z * z2z2;
+        let s2 = other.y * self.z * z1z1;
+        let h = u2 - u1;
+        let r = s2 - s1;
+
+        if h.is_zero() {
+            return if r.is_zero() {
+                self.double()
+            } else {
+                Self::infinity(self.curve)
+            };
+        }
+
+        let hh = h * h;
+        let hhh = h * hh;
+        let v = u1 * hh;
+        let x = r * r - hhh - v * 2;
+        let y = r * (v - x) - s1 * hhh;
+        let z = self.z * other.z * h;
+
+        JacobianPoint { x, y, z, ..self }
+    }
+}
+
+/// Montgomery's trick: the product of all values is inverted once, and each
+/// inverse is recovered from it and the prefix products, so n inversions
+/// cost one inversion and 3(n - 1) multiplications. No value may be zero.
+fn batch_invert<G: GraphPoint>(values: &[G]) -> Vec<G> {
+    let Some(&first) = values.first() else {
+        return Vec::new();
+    };
+
+    // prefixes[i] is the product of the values before i.
+    let mut prefixes = Vec::with_capacity(values.len());
+    let mut product = one_like(first);
+    for &value in values {
+        prefixes.push(product);
+        product = product * value;
+    }
+
+    let mut inverse = one_like(product) / product;
+    for (prefix, &value) in prefixes.iter_mut().zip(values).rev() {
+        *prefix = *prefix * inverse;
+        inverse = inverse * value;
+    }
+
+    prefixes
+}
+
+/// Window width used by [`wnaf_mul`]: digits are odd and in
+/// ±(2^(w-1) - 1), so 2^(w-2) multiples of the point are precomputed.
+const WNAF_WIDTH: u32 = 5;
+
+/// Width-`width` non-adjacent form of the scalar whose `bits` lowest bits are
+/// given by `bit`, least significant digit first. Every non-zero digit is odd
+/// and followed by at least `width - 1` zeros.
+fn wnaf(bits: u32, bit: impl Fn(u32) -> bool, width: u32) -> Vec<i32> {
+    let get = |i: u32| i < bits && bit(i);
+    // One extra digit absorbs the carry out of the top window.
+    let length = bits + 1;
+    let mut digits = vec![0; length as usize];
+    let mut carry = 0;
+    let mut i = 0;
+
+    while i < length {
+        if get(i) as i32 == carry {
+            i += 1;
+            continue;
+        }
+
+        let window = width.min(length - i);
+        let mut word = (0..window).fold(0, |word, j| word | (get(i + j) as i32) << j) + carry;
+        carry = (word >> (width - 1)) & 1;
+        word -= carry << width;
+        digits[i as usize] = word;
+        i += window;
+    }
+
+    digits
+}
+
+/// P, 3P, ..., (2^(w-1) - 1)P for the odd wNAF digits.
+fn odd_multiples<G: GraphPoint>(base: JacobianPoint<G>) -> Vec<JacobianPoint<G>> {
+    let doubled = base.double();
+    let mut table = vec![base];
+    for _ in 1..1 << (WNAF_WIDTH - 2) {
+        table.push(*table.last().unwrap() + doubled);
+    }
+    table
+}
+
+fn add_digit<G: GraphPoint>(
+    result: JacobianPoint<G>,
+    table: &[JacobianPoint<G>],
+    digit: i32,
+) -> JacobianPoint<G> {
+    match digit {
+        0 => result,
+        d if d > 0 => result + table[(d / 2) as usize],
+        d => result + table[(-d / 2) as usize].negate(),
+    }
+}
+
+/// Scalar multiplication with a width-5 NAF: odd multiples P, 3P, ..., 15P
+/// are precomputed and each non-zero digit costs one addition, which happens
+/// about once every six bits instead of every other bit.
+pub(crate) fn wnaf_mul<G: GraphPoint>(
+    point: Point<G>,
+    bits: u32,
+    bit: impl Fn(u32) -> bool,
+) -> Point<G> {
+    let table = odd_multiples(JacobianPoint::from(point));
+    let mut result = JacobianPoint::infinity(point.curve());
+    for digit in wnaf(bits, bit, WNAF_WIDTH).into_iter().rev() {
+        result = add_digit(result.double(), &table, digit);
+    }
+
+    result.to_affine()
+}
+
+/// Strauss–Shamir: u·P + v·Q sharing a single chain of doublings, with the
+/// wNAF digits of both scalars interleaved. Both points must be on the same
+/// curve.
+pub(crate) fn double_scalar_mul<G: GraphPoint>(
+    u: U256,
+    p: Point<G>,
+    v: U256,
+    q: Point<G>,
+) -> Point<G> {
+    let p_table = odd_multiples(JacobianPoint::from(p));
+    let q_table = odd_multiples(JacobianPoint::from(q));
+    let mut u_digits = wnaf(u.bits(), |i| u.bit(i), WNAF_WIDTH);
+    let mut v_digits = wnaf(v.bits(), |i| v.bit(i), WNAF_WIDTH);
+    let length = u_digits.len().max(v_digits.len());
+    u_digits.resize(length, 0);
+    v_digits.resize(length, 0);
+
+    let mut result = JacobianPoint::infinity(p.curve());
+    for (u_digit, v_digit) in u_digits.into_iter().zip(v_digits).rev() {
+        result = add_digit(result.double(), &p_table, u_digit);
+        result = add_digit(result, &q_table, v_digit);
+    }
+
+    result.to_affine()
+}
+
+/// Double-and-add over the `bits` lowest bits of a scalar, most significant
+/// first, with `bit(i)` telling whether bit i is set. Kept as a reference for
+/// [`wnaf_mul`].
+#[cfg(test)]
+pub(crate) fn scalar_mul<G: GraphPoint>(
+    point: Point<G>,
+    bits: u32,
+    bit: impl Fn(u32) -> bool,
+) -> Point<G> {
+    crate::representation::scalar_mul::<JacobianPoint<G>, G>(point, bits, bit)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field_element::FieldElement;
+
+    fn points() -> Vec<Point<FieldElement>> {
+        let generator = Point::from_finite_field(47, 71, 0, 7, 223).unwrap();
+        let mut multiples = vec![generator.curve().infinity()];
+        for _ in 1..21 {
+            multiples.push((*multiples.last().unwrap() + generator).unwrap());
+        }
+        multiples
+    }
+
+    #[test]
+    fn converts_to_and_from_affine() {
+        for point in points() {
+            assert_eq!(JacobianPoint::from(point).to_affine(), point);
+        }
+    }
+
+    #[test]
+    fn addition_and_doubling_match_affine() {
+        let points = points();
+        for &p in &points {
+            assert_eq!(
+                JacobianPoint::from(p).double().to_affine(),
+                (p + p).unwrap()
+            );
+            for &q in &points {
+                let sum = JacobianPoint::from(p) + JacobianPoint::from(q);
+                assert_eq!(sum.to_affine(), (p + q).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn addition_handles_non_normalized_z() {
+        let points = points();
+        let doubled = JacobianPoint::from(points[3]).double();
+        let sum = doubled + JacobianPoint::from(points[5]).double();
+        assert_eq!(sum.to_affine(), points[16]);
+        assert!((doubled + JacobianPoint::from(points[15])).to_affine() == points[0]);
+    }
+
+    #[test]
+    fn batch_normalization_matches_individual_conversion() {
+        let jacobian: Vec<_> = points()
+            .into_iter()
+            .map(|point| JacobianPoint::from(point).double() + JacobianPoint::from(point))
+            .collect();
+        let expected: Vec<_> = jacobian.iter().map(|point| point.to_affine()).collect();
+
+        assert!(jacobian[0].is_infinity());
+        assert_eq!(JacobianPoint::to_affine_batch(&jacobian), expected);
+        assert!(JacobianPoint::<FieldElement>::to_affine_batch(&[]).is_empty());
+    }
+
+    #[test]
+    fn batch_invert_inverts_every_value() {
+        let values: Vec<_> = (1..20)
+            .map(|n| FieldElement::new(n, 223).unwrap())
+            .collect();
+        for (value, inverse) in values.iter().zip(batch_invert(&values)) {
+            assert_eq!(value.inverse(), inverse);
+        }
+    }
+
+    fn scalar_from_wnaf(digits: &[i32]) -> i64 {
+        digits
+            .iter()
+            .rev()
+            .fold(0, |k, &digit| 2 * k + digit as i64)
+    }
+
+    #[test]
+    fn wnaf_digits_are_sparse_odd_and_sum_to_the_scalar() {
+        for k in (0..2000u32).chain([u32::MAX, 1 << 31, 0xdeadbeef]) {
+            let bits = u32::BITS - k.leading_zeros();
+            let digits = wnaf(bits, |i| k & (1 << i) != 0, WNAF_WIDTH);
+            assert_eq!(scalar_from_wnaf(&digits), k as i64, "{}", k);
+
+            let non_zero: Vec<_> = (0..digits.len()).filter(|&i| digits[i] != 0).collect();
+            for &i in &non_zero {
+                assert!(digits[i] % 2 != 0 && digits[i].abs() < 1 << (WNAF_WIDTH - 1));
+            }
+            for pair in non_zero.windows(2) {
+                assert!(pair[1] - pair[0] >= WNAF_WIDTH as usize);
+            }
+        }
+    }
+
+    #[test]
+    fn wnaf_multiplication_matches_double_and_add() {
+        let generator = Point::from_finite_field(47, 71, 0, 7, 223).unwrap();
+        for k in 0..100u32 {
+            let bits = u32::BITS - k.leading_zeros();
+            assert_eq!(
+                wnaf_mul(generator, bits, |i| k & (1 << i) != 0),
+                scalar_mul(generator, bits, |i| k & (1 << i) != 0),
+                "{}",
+                k
+            );
+        }
+    }
+
+    #[test]
+    fn double_scalar_multiplication_matches_separate_products() {
+        let p = Point::from_finite_field(47, 71, 0, 7, 223).unwrap();
+        let q = 5 * p;
+        let infinity = p.curve().infinity();
+        for u in [0u32, 1, 2, 7, 20, 21, 1000] {
+            for v in [0u32, 1, 3, 16, 21, 12345] {
+                let expected = ((u * p) + (v * q)).unwrap();
+                assert_eq!(
+                    double_scalar_mul(U256::from(u), p, U256::from(v), q),
+                    expected
+                );
+            }
+        }
+        assert_eq!(
+            double_scalar_mul(U256::from(3), p, U256::from(3), infinity),
+            3 * p
+        );
+    }
+
+    #[test]
+    fn negation_matches_affine() {
+        for point in points() {
+            let negated = JacobianPoint::from(point).negate();
+            assert!((negated + JacobianPoint::from(point)).is_infinity());
+        }
+    }
+
+    #[test]
+    fn doubling_works_on_curves_with_non_zero_a() {
+        let p = Point::from_finite_field(3, 6, 2, 3, 97).unwrap();
+        let affine = (p + p).unwrap();
+        assert_eq!(JacobianPoint::from(p).double().to_affine(), affine);
+        assert_eq!(
+            scalar_mul(p, 3, |i| 5 & (1 << i) != 0),
+            ((affine + affine).unwrap() + p).unwrap()
+        );
+    }
+}
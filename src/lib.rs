@@ -0,0 +1,39 @@
+//! Finite fields, elliptic curves and the secp256k1 primitives from
+//! "Programming Bitcoin", written to be reused. Most code only needs the
+//! [`prelude`].
+
+pub mod algebra;
+#[cfg(feature = "constant-time")]
+pub mod constant_time;
+pub mod coordinate;
+pub mod crypto;
+pub mod curve;
+pub mod curve_form;
+pub mod descriptor_checksum;
+pub mod ecdh;
+pub mod explain;
+pub mod field_element;
+pub mod field_uint;
+mod forward_ref;
+pub mod fp;
+pub mod hash_to_curve;
+pub mod is_zero;
+pub mod jacobian;
+pub mod named_curve;
+pub mod number_theory;
+pub mod point;
+pub mod polynomial;
+pub mod pow;
+pub mod prelude;
+pub mod real_value;
+pub mod representation;
+pub mod s256_field;
+pub mod s256_point;
+pub mod scalar;
+#[cfg(feature = "serde")]
+mod serialization;
+pub mod shamir;
+pub mod subsidy;
+pub mod try_ops;
+pub mod u256;
+pub mod validation;
@@ -1,28 +1,23 @@
-use coordinate::Coordinate;
-use point::Point;
+use std::iter::once;
 
-mod coordinate;
-mod field_element;
-mod is_zero;
-mod point;
-mod pow;
-mod real_value;
+use programming_bitcoin::prelude::*;
 
 fn main() -> Result<(), String> {
     let p1 = Point::from_finite_field(47, 71, 0, 7, 223).unwrap();
 
-    for i in 0..22 {
-        let res = i * p1;
+    // The multiples stop before wrapping around; print 21*(47,71) as well.
+    let multiples = p1.multiples().chain(once(p1.curve().infinity()));
+    for (i, res) in multiples.enumerate() {
         let x = if let Coordinate::Value(x) = res.x {
             x.number
         } else {
-            0
+            U256::ZERO
         };
 
         let y = if let Coordinate::Value(y) = res.y {
             y.number
         } else {
-            0
+            U256::ZERO
         };
         println!("{i}*(47,71) = ({x},{y})");
     }
@@ -0,0 +1,197 @@
+//! Standard curves over 256-bit prime fields, described by their constants so
+//! the generic field and point code can be instantiated for each of them.
+
+use crate::{
+    curve::Curve,
+    field_element::FieldElement,
+    point::Point,
+    s256_field,
+    s256_point::{self, N},
+    u256::U256,
+};
+
+pub trait NamedCurve {
+    const NAME: &'static str;
+    /// Field prime.
+    const P: U256;
+    const A: U256;
+    const B: U256;
+    const GX: U256;
+    const GY: U256;
+    /// Order of the generator.
+    const N: U256;
+
+    /// The constants are known to be valid, so P is not tested for
+    /// primality on every call.
+    fn curve() -> Curve<FieldElement> {
+        Curve::new(
+            FieldElement::new_unchecked(Self::A, Self::P),
+            FieldElement::new_unchecked(Self::B, Self::P),
+        )
+    }
+
+    fn generator() -> Point<FieldElement> {
+        Self::curve()
+            .point_from_numbers(Self::GX, Self::GY)
+            .unwrap()
+    }
+
+    /// `coefficient`·`point` with the coefficient reduced modulo N first.
+    /// Both curves here have prime order, so this holds for any point on
+    /// them.
+    fn multiply(coefficient: U256, point: Point<FieldElement>) -> Point<FieldElement> {
+        coefficient.div_rem(Self::N).1 * point
+    }
+}
+
+/// The Bitcoin curve, y^2 = x^3 + 7. [`s256_point::S256Point`] is the
+/// specialized implementation.
+pub struct Secp256k1;
+
+impl NamedCurve for Secp256k1 {
+    const NAME: &'static str = "secp256k1";
+    const P: U256 = s256_field::P;
+    const A: U256 = U256::ZERO;
+    const B: U256 = U256::from_u64(7);
+    const GX: U256 = s256_point::GX;
+    const GY: U256 = s256_point::GY;
+    const N: U256 = N;
+}
+
+/// NIST P-256, also known as secp256r1 (FIPS 186-4, appendix D.1.2.3).
+pub struct P256;
+
+impl NamedCurve for P256 {
+    const NAME: &'static str = "P-256";
+    const P: U256 = U256::from_limbs([
+        0xFFFFFFFFFFFFFFFF,
+        0x00000000FFFFFFFF,
+        0x0000000000000000,
+        0xFFFFFFFF00000001,
+    ]);
+    const A: U256 = U256::from_limbs([
+        0xFFFFFFFFFFFFFFFC,
+        0x00000000FFFFFFFF,
+        0x0000000000000000,
+        0xFFFFFFFF00000001,
+    ]);
+    const B: U256 = U256::from_limbs([
+        0x3BCE3C3E27D2604B,
+        0x651D06B0CC53B0F6,
+        0xB3EBBD55769886BC,
+        0x5AC635D8AA3A93E7,
+    ]);
+    const GX: U256 = U256::from_limbs([
+        0xF4A13945D898C296,
+        0x77037D812DEB33A0,
+        0xF8BCE6E563A440F2,
+        0x6B17D1F2E12C4247,
+    ]);
+    const GY: U256 = U256::from_limbs([
+        0xCBB6406837BF51F5,
+        0x2BCE33576B315ECE,
+        0x8EE7EB4A7C0F9E16,
+        0x4FE342E2FE1A7F9B,
+    ]);
+    const N: U256 = U256::from_limbs([
+        0xF3B9CAC2FC632551,
+        0xBCE6FAADA7179E84,
+        0xFFFFFFFFFFFFFFFF,
+        0xFFFFFFFF00000000,
+    ]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::s256_point::S256Point;
+
+    fn hex(value: &str) -> U256 {
+        U256::from_hex(value).unwrap()
+    }
+
+    fn generator_has_order_n<C: NamedCurve>() {
+        assert_eq!(
+            Curve::from_finite_field(C::A, C::B, C::P),
+            Ok(C::curve()),
+            "{}",
+            C::NAME
+        );
+
+        let generator = C::generator();
+        assert!((C::N * generator).x.is_infinity(), "{}", C::NAME);
+        assert_eq!(
+            ((C::N - U256::ONE) * generator + generator).unwrap(),
+            C::curve().infinity()
+        );
+    }
+
+    #[test]
+    fn generators_have_order_n() {
+        generator_has_order_n::<Secp256k1>();
+        generator_has_order_n::<P256>();
+    }
+
+    #[test]
+    fn constants_match_published_hex() {
+        assert_eq!(
+            P256::P,
+            hex("ffffffff00000001000000000000000000000000ffffffffffffffffffffffff")
+        );
+        assert_eq!(P256::A, P256::P - U256::from(3));
+        assert_eq!(
+            P256::B,
+            hex("5ac635d8aa3a93e7b3ebbd55769886bc651d06b0cc53b0f63bce3c3e27d2604b")
+        );
+        assert_eq!(
+            P256::N,
+            hex("ffffffff00000000ffffffffffffffffbce6faada7179e84f3b9cac2fc632551")
+        );
+    }
+
+    #[test]
+    fn p256_multiplication_matches_known_vectors() {
+        let curve = P256::curve();
+        let cases = [
+            (
+                U256::from(2),
+                "7cf27b188d034f7e8a52380304b51ac3c08969e277f21b35a60b48fc47669978",
+                "07775510db8ed040293d9ac69f7430dbba7dade63ce982299e04b79d227873d1",
+            ),
+            (
+                U256::from_u64(112233445566778899),
+                "339150844ec15234807fe862a86be77977dbfb3ae3d96f4c22795513aeaab82f",
+                "b1c14ddfdc8ec1b2583f51e85a5eb3a155840f2034730e9b5ada38b674336a21",
+            ),
+        ];
+
+        for (k, x, y) in cases {
+            let expected = curve.point_from_numbers(hex(x), hex(y)).unwrap();
+            assert_eq!(k * P256::generator(), expected);
+        }
+    }
+
+    #[test]
+    fn secp256k1_matches_specialized_point() {
+        let generic: Point<FieldElement> = S256Point::G.into();
+        assert_eq!(Secp256k1::generator(), generic);
+        let seven: Point<FieldElement> = (7 * S256Point::G).into();
+        assert_eq!(U256::from(7) * Secp256k1::generator(), seven);
+    }
+
+    #[test]
+    fn multiply_reduces_coefficient_modulo_order() {
+        let generator = P256::generator();
+        let k = U256::from_u64(112233445566778899);
+        assert_eq!(P256::multiply(k + P256::N, generator), k * generator);
+        assert_eq!(
+            Secp256k1::multiply(U256::MAX, Secp256k1::generator()),
+            (U256::MAX - Secp256k1::N) * Secp256k1::generator()
+        );
+    }
+
+    #[test]
+    fn points_of_different_curves_cannot_be_added() {
+        assert!((Secp256k1::generator() + P256::generator()).is_err());
+    }
+}
@@ -0,0 +1,130 @@
+//! Number-theory helpers on 256-bit integers, independent of the field types
+//! so they can double as an oracle for them.
+
+use crate::{field_uint::FieldUint, u256::U256};
+
+pub fn gcd(mut a: U256, mut b: U256) -> U256 {
+    while !b.is_zero() {
+        (a, b) = (b, a % b);
+    }
+
+    a
+}
+
+/// Least common multiple, or None if it does not fit in 256 bits.
+pub fn lcm(a: U256, b: U256) -> Option<U256> {
+    if a.is_zero() || b.is_zero() {
+        return Some(U256::ZERO);
+    }
+
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// Inverse of `a` modulo `modulus`, which only exists if they are coprime.
+pub fn mod_inverse(a: U256, modulus: U256) -> Option<U256> {
+    if modulus <= U256::ONE {
+        return None;
+    }
+
+    a.inv_mod(modulus)
+}
+
+/// Solves x = `residues[i]` mod `moduli[i]` for pairwise coprime moduli,
+/// returning x together with the product of the moduli, below which x is
+/// unique.
+pub fn crt(residues: &[U256], moduli: &[U256]) -> Result<(U256, U256), String> {
+    if residues.len() != moduli.len() {
+        return Err(format!(
+            "Got {} residues for {} moduli",
+            residues.len(),
+            moduli.len()
+        ));
+    }
+
+    let mut solution = U256::ZERO;
+    let mut product = U256::ONE;
+    for (&residue, &modulus) in residues.iter().zip(moduli) {
+        if modulus.is_zero() {
+            return Err("Moduli must be positive".to_string());
+        }
+
+        let combined = product
+            .checked_mul(modulus)
+            .ok_or("Product of the moduli does not fit in 256 bits")?;
+
+        if modulus == U256::ONE {
+            product = combined;
+            continue;
+        }
+
+        let inverse = mod_inverse(product % modulus, modulus)
+            .ok_or_else(|| format!("{} is not coprime with the previous moduli", modulus))?;
+
+        // solution + product * t hits residue mod modulus for
+        // t = (residue - solution) / product mod modulus.
+        let difference = (residue % modulus).sub_mod(solution % modulus, modulus);
+        let t = difference.mul_mod(inverse, modulus);
+        solution = solution + product * t;
+        product = combined;
+    }
+
+    Ok((solution, product))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field_element::FieldElement;
+
+    fn values(numbers: &[u32]) -> Vec<U256> {
+        numbers.iter().map(|&x| U256::from(x)).collect()
+    }
+
+    #[test]
+    fn gcd_and_lcm_of_small_numbers() {
+        assert_eq!(gcd(U256::from(48), U256::from(18)), U256::from(6));
+        assert_eq!(gcd(U256::ZERO, U256::from(5)), U256::from(5));
+        assert_eq!(lcm(U256::from(4), U256::from(6)), Some(U256::from(12)));
+        assert_eq!(lcm(U256::ZERO, U256::from(6)), Some(U256::ZERO));
+        assert_eq!(lcm(U256::MAX, U256::from(2)), None);
+    }
+
+    #[test]
+    fn crt_recovers_classic_example() {
+        // Sunzi: x = 2 mod 3, 3 mod 5, 2 mod 7.
+        let (x, modulus) = crt(&values(&[2, 3, 2]), &values(&[3, 5, 7])).unwrap();
+        assert_eq!((x, modulus), (U256::from(23), U256::from(105)));
+    }
+
+    #[test]
+    fn crt_reconstructs_number_from_prime_residues() {
+        let primes = values(&[4_294_967_291, 4_294_967_279, 4_294_967_231, 4_294_967_197]);
+        let number = U256::from_hex("fedcba9876543210fedcba9876543210").unwrap();
+        let residues: Vec<U256> = primes.iter().map(|&p| number % p).collect();
+
+        assert_eq!(crt(&residues, &primes).unwrap().0, number);
+    }
+
+    #[test]
+    fn crt_rejects_invalid_systems() {
+        assert!(crt(&values(&[1, 2]), &values(&[4, 6])).is_err());
+        assert!(crt(&values(&[1]), &values(&[4, 6])).is_err());
+        assert!(crt(&values(&[1]), &values(&[0])).is_err());
+        assert!(crt(&[U256::ONE, U256::ONE], &[U256::MAX, U256::MAX - U256::ONE]).is_err());
+        assert_eq!(crt(&[], &[]), Ok((U256::ZERO, U256::ONE)));
+    }
+
+    #[test]
+    fn modular_inverse_matches_field_inverse() {
+        for x in 1..223 {
+            let element = FieldElement::new(x, 223).unwrap();
+            assert_eq!(
+                mod_inverse(U256::from(x), U256::from(223)),
+                Some(element.inverse().number)
+            );
+        }
+
+        assert_eq!(mod_inverse(U256::from(6), U256::from(9)), None);
+        assert_eq!(mod_inverse(U256::from(6), U256::ONE), None);
+    }
+}
@@ -1,29 +1,39 @@
 use std::{
-    fmt::Display,
-    ops::{Add, Mul},
+    fmt::{Display, Formatter, LowerHex, Result as FmtResult, UpperHex},
+    iter::Sum,
+    ops::{Add, AddAssign, Mul, MulAssign, Neg, Sub},
 };
 
 use crate::{
+    algebra::{Group, Monoid},
     coordinate::{Coordinate, GraphPoint},
+    curve::Curve,
+    explain::explain,
     field_element::FieldElement,
+    forward_ref::{forward_ref_binop, forward_ref_op_assign},
+    jacobian::wnaf_mul,
     pow::Pow,
+    u256::U256,
 };
 
+mod order;
+
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
 pub struct Point<G: GraphPoint> {
     pub x: Coordinate<G>,
     pub y: Coordinate<G>,
-    a: Coordinate<G>,
-    b: Coordinate<G>,
+    curve: Curve<G>,
 }
 
 impl Point<FieldElement> {
-    pub fn from_finite_field(x: u32, y: u32, a: u32, b: u32, prime: u32) -> Result<Self, String> {
-        let x = Coordinate::Value(FieldElement::new(x, prime)?);
-        let y = Coordinate::Value(FieldElement::new(y, prime)?);
-        let a = Coordinate::Value(FieldElement::new(a, prime)?);
-        let b = Coordinate::Value(FieldElement::new(b, prime)?);
-        Self::new(x, y, a, b)
+    pub fn from_finite_field<N: Into<U256>>(
+        x: N,
+        y: N,
+        a: N,
+        b: N,
+        prime: N,
+    ) -> Result<Self, String> {
+        Curve::from_finite_field(a, b, prime)?.point_from_numbers(x, y)
     }
 }
 
@@ -34,60 +44,178 @@ impl<G: GraphPoint> Point<G> {
         a: B,
         b: B,
     ) -> Result<Self, String> {
-        let x: Coordinate<G> = x.into();
-        let y: Coordinate<G> = y.into();
-        let a: Coordinate<G> = a.into();
-        let b: Coordinate<G> = b.into();
+        match (a.into(), b.into()) {
+            (Coordinate::Value(a), Coordinate::Value(b)) => {
+                Self::on_curve(x.into(), y.into(), Curve::new(a, b))
+            }
+            _ => Err("Curve parameters cannot be infinite".to_string()),
+        }
+    }
+
+    /// Whether (x, y) satisfies y^2 = x^3 + ax + b, without building the
+    /// point. Infinite curve parameters never describe a curve.
+    pub fn is_on_curve<A: Into<Coordinate<G>>, B: Into<Coordinate<G>>>(
+        x: A,
+        y: A,
+        a: B,
+        b: B,
+    ) -> bool {
+        match (a.into(), b.into()) {
+            (Coordinate::Value(a), Coordinate::Value(b)) => {
+                Curve::new(a, b).contains(x.into(), y.into())
+            }
+            _ => false,
+        }
+    }
 
-        if !(x.is_infinity() && y.is_infinity()) && y.pow(2) != x.pow(3) + x * a + b {
-            return Err(format!("({},{}) is not on the curve", x, y));
+    pub fn on_curve(x: Coordinate<G>, y: Coordinate<G>, curve: Curve<G>) -> Result<Self, String> {
+        if !curve.contains(x, y) {
+            return Err(format!("({},{}) is not on the curve {}", x, y, curve));
         }
 
-        Ok(Self { x, y, a, b })
+        Ok(Self { x, y, curve })
+    }
+
+    /// For constants known to be on the curve.
+    pub(crate) const fn from_parts(x: Coordinate<G>, y: Coordinate<G>, curve: Curve<G>) -> Self {
+        Point { x, y, curve }
+    }
+
+    pub fn curve(&self) -> Curve<G> {
+        self.curve
+    }
+
+    pub fn a(&self) -> G {
+        self.curve.a()
+    }
+
+    pub fn b(&self) -> G {
+        self.curve.b()
+    }
+
+    /// Iterates over 0·self, 1·self, 2·self, ... and stops before returning
+    /// to the point at infinity, so on a finite field the number of items is
+    /// the order of self. Points over the reals never return there.
+    pub fn multiples(self) -> Multiples<G> {
+        Multiples {
+            base: self,
+            next: Some(self.curve.infinity()),
+        }
     }
 
     fn add_point(self, other: Self) -> Self {
         if self.x == other.x && self.y != other.y {
-            return Point {
-                x: Coordinate::Infinity,
-                y: Coordinate::Infinity,
-                a: self.a,
-                b: self.b,
-            };
+            return self.curve.infinity();
         }
 
         let slope = if self == other {
             if self.y.is_zero() {
-                return Point {
-                    x: Coordinate::Infinity,
-                    y: Coordinate::Infinity,
-                    a: self.a,
-                    b: self.b,
-                };
+                return self.curve.infinity();
             }
 
-            (self.x.pow(2) * 3 + self.a) / (self.y * 2)
+            let slope = (self.x.pow(2) * 3 + Coordinate::Value(self.a())) / (self.y * 2);
+            explain!("slope = (3 * x1^2 + a) / (2 * y1) = {}", slope);
+            slope
         } else {
-            (other.y - self.y) / (other.x - self.x)
+            let slope = (other.y - self.y) / (other.x - self.x);
+            explain!("slope = (y2 - y1) / (x2 - x1) = {}", slope);
+            slope
         };
 
         let x_res = slope.pow(2) - self.x - other.x;
+        explain!("x3 = slope^2 - x1 - x2 = {}", x_res);
         let y_res = slope * (self.x - x_res) - self.y;
+        explain!("y3 = slope * (x1 - x3) - y1 = {}", y_res);
 
-        let res = Point {
-            a: self.a,
-            b: self.b,
+        Point {
             x: x_res,
             y: y_res,
+            curve: self.curve,
+        }
+    }
+}
+
+/// Panics when combining points of different curves, like `+=`.
+impl<G: GraphPoint> Monoid for Point<G> {
+    fn identity(&self) -> Self {
+        self.curve.infinity()
+    }
+
+    fn combine(self, other: Self) -> Self {
+        (self + other).unwrap_or_else(|e| panic!("{}", e))
+    }
+}
+
+impl<G: GraphPoint> Group for Point<G> {
+    fn inverse(self) -> Self {
+        -self
+    }
+}
+
+/// Iterator returned by [`Point::multiples`].
+pub struct Multiples<G: GraphPoint> {
+    base: Point<G>,
+    next: Option<Point<G>>,
+}
+
+impl<G: GraphPoint> Iterator for Multiples<G> {
+    type Item = Point<G>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.next?;
+        // Both points are on the same curve, so the addition cannot fail.
+        let following = (current + self.base).unwrap();
+        self.next = if following.x.is_infinity() {
+            None
+        } else {
+            Some(following)
         };
 
-        return res;
+        Some(current)
+    }
+}
+
+impl<G: GraphPoint> TryFrom<(Coordinate<G>, Coordinate<G>, Curve<G>)> for Point<G> {
+    type Error = String;
+
+    fn try_from(
+        (x, y, curve): (Coordinate<G>, Coordinate<G>, Curve<G>),
+    ) -> Result<Self, Self::Error> {
+        Point::on_curve(x, y, curve)
     }
 }
 
 impl<G: GraphPoint> Display for Point<G> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "Point({},{})_{}_{}", self.x, self.y, self.a, self.b)
+        write!(f, "Point({},{})_{}_{}", self.x, self.y, self.a(), self.b())
+    }
+}
+
+/// Hex formats each coordinate with the caller's flags, so `{:064x}` pads
+/// all four of them.
+impl<G: GraphPoint + LowerHex> LowerHex for Point<G> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "Point(")?;
+        LowerHex::fmt(&self.x, f)?;
+        write!(f, ",")?;
+        LowerHex::fmt(&self.y, f)?;
+        write!(f, ")_")?;
+        LowerHex::fmt(&self.a(), f)?;
+        write!(f, "_")?;
+        LowerHex::fmt(&self.b(), f)
+    }
+}
+
+impl<G: GraphPoint + UpperHex> UpperHex for Point<G> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "Point(")?;
+        UpperHex::fmt(&self.x, f)?;
+        write!(f, ",")?;
+        UpperHex::fmt(&self.y, f)?;
+        write!(f, ")_")?;
+        UpperHex::fmt(&self.a(), f)?;
+        write!(f, "_")?;
+        UpperHex::fmt(&self.b(), f)
     }
 }
 
@@ -95,7 +223,7 @@ impl<G: GraphPoint> Add for Point<G> {
     type Output = Result<Self, String>;
 
     fn add(self, other: Self) -> Self::Output {
-        if self.a != other.a || self.b != other.b {
+        if self.curve != other.curve {
             return Err(format!(
                 "Points {}, {} are not on the same curve",
                 self, other
@@ -110,26 +238,74 @@ impl<G: GraphPoint> Add for Point<G> {
             return Ok(self);
         }
 
-        return Ok(self.add_point(other));
+        Ok(self.add_point(other))
     }
 }
 
-fn binary_expansion<T: GraphPoint>(point: Point<T>, coefficient: u32) -> Point<T> {
-    let mut coef = coefficient;
-    let mut current = point;
-    let mut result =
-        Point::new(Coordinate::Infinity, Coordinate::Infinity, point.a, point.b).unwrap();
-
-    while coef > 0 {
-        if coef & 1 == 1 {
-            result = (result + current).unwrap();
+forward_ref_binop!(impl<G: GraphPoint> Add, add for Point<G>);
+
+/// Reflects the point over the x-axis; the point at infinity is its own
+/// negation.
+impl<G: GraphPoint> Neg for Point<G> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Point {
+            y: self.y * -1,
+            ..self
         }
+    }
+}
+
+impl<G: GraphPoint> Sub for Point<G> {
+    type Output = Result<Self, String>;
+
+    fn sub(self, other: Self) -> Self::Output {
+        self + -other
+    }
+}
 
-        current = (current + current).unwrap();
-        coef >>= 1;
+forward_ref_binop!(impl<G: GraphPoint> Sub, sub for Point<G>);
+
+/// Panics if the points are not on the same curve, like the field element
+/// operators do for mismatched primes. Use `+` to get a `Result` instead.
+impl<G: GraphPoint> AddAssign for Point<G> {
+    fn add_assign(&mut self, other: Self) {
+        *self = (*self + other).unwrap_or_else(|e| panic!("{}", e));
+    }
+}
+
+forward_ref_op_assign!(impl<G: GraphPoint> AddAssign, add_assign for Point<G>);
+
+/// Panics on an empty iterator, since the curve of the point at infinity
+/// would be unknown, and on points of different curves.
+impl<G: GraphPoint> Sum for Point<G> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.reduce(|mut acc, point| {
+            acc += point;
+            acc
+        })
+        .expect("Cannot sum an empty iterator of points")
+    }
+}
+
+impl<'a, G: GraphPoint> Sum<&'a Self> for Point<G> {
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.copied().sum()
     }
+}
+
+fn windowed_naf<T: GraphPoint>(point: Point<T>, coefficient: u32) -> Point<T> {
+    wnaf_mul(point, u32::BITS - coefficient.leading_zeros(), |i| {
+        coefficient & (1 << i) != 0
+    })
+}
 
-    return result;
+#[cfg(test)]
+fn binary_expansion<T: GraphPoint>(point: Point<T>, coefficient: u32) -> Point<T> {
+    crate::jacobian::scalar_mul(point, u32::BITS - coefficient.leading_zeros(), |i| {
+        coefficient & (1 << i) != 0
+    })
 }
 
 impl<T: GraphPoint> Mul<Point<T>> for u32 {
@@ -144,7 +320,45 @@ impl<T: GraphPoint> Mul<Point<T>> for u32 {
         // }
         // result
 
-        binary_expansion(other, self)
+        windowed_naf(other, self)
+    }
+}
+
+impl<'a, T: GraphPoint> Mul<&'a Point<T>> for u32 {
+    type Output = Point<T>;
+
+    fn mul(self, other: &'a Point<T>) -> Self::Output {
+        self * *other
+    }
+}
+
+impl<T: GraphPoint> MulAssign<u32> for Point<T> {
+    fn mul_assign(&mut self, coefficient: u32) {
+        *self = coefficient * *self;
+    }
+}
+
+/// The coefficient is used as is; reduce it modulo the group order first if
+/// it is known.
+impl<T: GraphPoint> Mul<Point<T>> for U256 {
+    type Output = Point<T>;
+
+    fn mul(self, other: Point<T>) -> Self::Output {
+        wnaf_mul(other, self.bits(), |i| self.bit(i))
+    }
+}
+
+impl<'a, T: GraphPoint> Mul<&'a Point<T>> for U256 {
+    type Output = Point<T>;
+
+    fn mul(self, other: &'a Point<T>) -> Self::Output {
+        self * *other
+    }
+}
+
+impl<T: GraphPoint> MulAssign<U256> for Point<T> {
+    fn mul_assign(&mut self, coefficient: U256) {
+        *self = coefficient * *self;
     }
 }
 
@@ -154,6 +368,34 @@ mod tests {
 
     use super::*;
 
+    #[test]
+    fn hex_formatting_applies_to_every_coordinate() {
+        let point = Point::from_finite_field(47, 71, 0, 7, 223).unwrap();
+        assert_eq!(format!("{:x}", point), "Point(2f,47)_0_7");
+        assert_eq!(format!("{:#04X}", point), "Point(0x2F,0x47)_0x00_0x07");
+
+        let infinity = Point::new(
+            Coordinate::Infinity,
+            Coordinate::Infinity,
+            FieldElement::new(0, 223).unwrap(),
+            FieldElement::new(7, 223).unwrap(),
+        )
+        .unwrap();
+        assert_eq!(format!("{:x}", infinity), "Point(Inf,Inf)_0_7");
+    }
+
+    #[test]
+    fn arithmetic_over_f3_handles_zero_coordinates() {
+        // y^2 = x^3 + 2x + 1 over F3 has seven points, generated by (0, 1).
+        let curve = Curve::from_finite_field(2, 1, 3).unwrap();
+        let generator = curve.point_from_numbers(0, 1).unwrap();
+        assert!(curve.point_from_numbers(0, 0).is_err());
+        assert_eq!(3 * generator, curve.point_from_numbers(2, 2).unwrap());
+        assert_eq!(4 * generator, curve.point_from_numbers(2, 1).unwrap());
+        assert_eq!(7 * generator, curve.infinity());
+        assert_eq!(curve.count_points(), Ok(7));
+    }
+
     #[test]
     fn cannot_create_point_that_is_not_in_curve() {
         let point_res = Point::new(-1, -2, 5, 7);
@@ -177,6 +419,64 @@ mod tests {
         assert!(point_res.is_ok());
     }
 
+    #[test]
+    fn is_on_curve_checks_equation_without_building_point() {
+        assert!(Point::<RealValue>::is_on_curve(-1, -1, 5, 7));
+        assert!(!Point::<RealValue>::is_on_curve(-1, -2, 5, 7));
+        assert!(Point::<RealValue>::is_on_curve(
+            Coordinate::Infinity,
+            Coordinate::Infinity,
+            Coordinate::from(5),
+            Coordinate::from(7)
+        ));
+        assert!(!Point::<RealValue>::is_on_curve(
+            Coordinate::from(-1),
+            Coordinate::from(-1),
+            Coordinate::Infinity,
+            Coordinate::from(7)
+        ));
+
+        let prime = 223;
+        let element = |n: u32| FieldElement::new(n, prime).unwrap();
+        assert!(Point::is_on_curve(
+            element(47),
+            element(71),
+            element(0),
+            element(7)
+        ));
+        assert!(!Point::is_on_curve(
+            element(200),
+            element(119),
+            element(0),
+            element(7)
+        ));
+
+        // 26^2 = 7, so an infinite x would satisfy y^2 = x^3 + 7 if it were
+        // treated like a value.
+        let (a, b) = (Coordinate::Value(element(0)), Coordinate::Value(element(7)));
+        assert!(!Point::is_on_curve(
+            Coordinate::Infinity,
+            Coordinate::Value(element(26)),
+            a,
+            b
+        ));
+        assert!(!Point::is_on_curve(
+            Coordinate::Value(element(26)),
+            Coordinate::Infinity,
+            a,
+            b
+        ));
+        assert!(Point::new(Coordinate::Infinity, Coordinate::Value(element(26)), a, b).is_err());
+    }
+
+    #[test]
+    fn try_from_validates_coordinates() {
+        let curve = Curve::<RealValue>::new(5, 7);
+        let point = Point::try_from(((-1).into(), (-1).into(), curve));
+        assert_eq!(point, Point::new(-1, -1, 5, 7));
+        assert!(Point::try_from(((-1).into(), (-2).into(), curve)).is_err());
+    }
+
     #[test]
     fn cannot_add_points_in_different_curves() {
         let p1 = Point::new(-1, 1, 5, 7).unwrap();
@@ -300,6 +600,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::erasing_op)]
     fn scalar_multiplication_with_zero_is_point_at_infinity() {
         let prime = 223;
         let x = FieldElement::new(15, prime).unwrap();
@@ -342,4 +643,136 @@ mod tests {
 
         assert_eq!(res, expected);
     }
+
+    #[test]
+    fn scalar_multiplication_matches_binary_expansion() {
+        let point = Point::from_finite_field(15, 86, 0, 7, 223).unwrap();
+        for coefficient in [0, 1, 6, 7, 8, 31, 1000, u32::MAX] {
+            assert_eq!(coefficient * point, binary_expansion(point, coefficient));
+        }
+    }
+
+    #[test]
+    fn negated_point_adds_up_to_infinity() {
+        let point = Point::from_finite_field(47, 71, 0, 7, 223).unwrap();
+        let negated = -point;
+        assert_eq!(
+            negated,
+            Point::from_finite_field(47, 152, 0, 7, 223).unwrap()
+        );
+        assert_eq!((point + negated).unwrap(), point.curve().infinity());
+        assert_eq!(-point.curve().infinity(), point.curve().infinity());
+
+        let real = Point::new(-1, -1, 5, 7).unwrap();
+        assert_eq!(-real, Point::new(-1, 1, 5, 7).unwrap());
+    }
+
+    #[test]
+    fn subtraction_undoes_addition() {
+        let p1 = Point::from_finite_field(47, 71, 0, 7, 223).unwrap();
+        let p2 = 5 * p1;
+        let (borrowed1, borrowed2) = (&p1, &p2);
+        assert_eq!((p2 - p1).unwrap(), 4 * p1);
+        assert_eq!((p1 - p1).unwrap(), p1.curve().infinity());
+        assert_eq!((borrowed1 - borrowed2).unwrap(), 17 * p1);
+
+        let real = Point::new(-1, 1, 5, 7).unwrap();
+        assert_eq!((real - real).unwrap(), real.curve().infinity());
+        assert!((real - Point::new(0, 1, 1, 1).unwrap()).is_err());
+    }
+
+    #[test]
+    fn wide_coefficients_match_u32_coefficients() {
+        let point = Point::from_finite_field(47, 71, 0, 7, 223).unwrap();
+        assert_eq!(U256::from(13) * point, 13 * point);
+        let borrowed = &point;
+        assert_eq!(U256::from(13) * borrowed, 13 * point);
+        // Not reduced, but 2^128 = 4 (mod 21) and 21 is the order of the point.
+        assert_eq!((U256::ONE << 128) * point, 4 * point);
+
+        let mut scaled = point;
+        scaled *= U256::from(20);
+        assert_eq!(scaled, -point);
+    }
+
+    #[test]
+    fn multiples_cycle_through_the_generated_subgroup() {
+        let point = Point::from_finite_field(47, 71, 0, 7, 223).unwrap();
+        let multiples: Vec<_> = point.multiples().collect();
+        assert_eq!(multiples.len(), 21);
+        for (i, multiple) in multiples.iter().enumerate() {
+            assert_eq!(*multiple, i as u32 * point);
+        }
+
+        let point = Point::from_finite_field(15, 86, 0, 7, 223).unwrap();
+        assert_eq!(point.multiples().count(), 7);
+        assert_eq!(point.multiples().count() as u64, point.order().unwrap());
+    }
+
+    #[test]
+    fn multiples_of_infinity_only_yield_infinity() {
+        let infinity = Point::from_finite_field(47, 71, 0, 7, 223)
+            .unwrap()
+            .curve()
+            .infinity();
+        assert_eq!(infinity.multiples().collect::<Vec<_>>(), [infinity]);
+    }
+
+    #[test]
+    fn multiples_over_reals_continue() {
+        let point = Point::new(-1, -1, 5, 7).unwrap();
+        let third = point.multiples().nth(3).unwrap();
+        // Compared against the same additions, since floats are not exact.
+        assert_eq!(third, ((point + point).unwrap() + point).unwrap());
+    }
+
+    #[test]
+    fn add_assign_accumulates_points() {
+        let p1 = Point::from_finite_field(47, 71, 0, 7, 223).unwrap();
+        let mut accumulator = p1;
+        for _ in 0..4 {
+            accumulator += &p1;
+        }
+
+        assert_eq!(accumulator, 5 * p1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn cannot_add_assign_points_in_different_curves() {
+        let mut p1 = Point::new(-1, 1, 5, 7).unwrap();
+        p1 += Point::new(0, 1, 1, 1).unwrap();
+    }
+
+    #[test]
+    fn can_operate_on_point_references() {
+        let p1 = Point::from_finite_field(47, 71, 0, 7, 223).unwrap();
+        let p2 = Point::from_finite_field(17, 56, 0, 7, 223).unwrap();
+        let (borrowed1, borrowed2) = (&p1, &p2);
+        assert_eq!(borrowed1 + borrowed2, p1 + p2);
+        assert_eq!(3 * borrowed1, 3 * p1);
+
+        let mut scaled = p1;
+        scaled *= 7;
+        assert_eq!(scaled, 7 * p1);
+    }
+
+    #[test]
+    fn sum_of_points_matches_scalar_multiplication() {
+        let p1 = Point::from_finite_field(47, 71, 0, 7, 223).unwrap();
+        let points: Vec<_> = (1..=4).map(|i| i * p1).collect();
+
+        assert_eq!(points.iter().sum::<Point<FieldElement>>(), 10 * p1);
+        assert_eq!(points.into_iter().sum::<Point<FieldElement>>(), 10 * p1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn cannot_sum_points_in_different_curves() {
+        let points = [
+            Point::new(-1, 1, 5, 7).unwrap(),
+            Point::new(0, 1, 1, 1).unwrap(),
+        ];
+        points.iter().sum::<Point<RealValue>>();
+    }
 }
@@ -0,0 +1,137 @@
+use std::collections::HashMap;
+
+use crate::{
+    coordinate::Coordinate,
+    field_element::{prime_factors, FieldElement},
+    u256::U256,
+};
+
+use super::Point;
+
+/// Up to this bound on the group order the multiples are simply walked.
+const NAIVE_LIMIT: u64 = 64;
+
+fn key(point: &Point<FieldElement>) -> Option<(U256, U256)> {
+    match (point.x, point.y) {
+        (Coordinate::Value(x), Coordinate::Value(y)) => Some((x.number, y.number)),
+        _ => None,
+    }
+}
+
+impl Point<FieldElement> {
+    /// Smallest k > 0 with k·self at infinity. Uses baby-step giant-step up
+    /// to the Hasse bound p + 1 + 2√p, so time and memory grow with the
+    /// square root of p; this is meant for small curves like the ones over
+    /// F223, not for secp256k1.
+    pub fn order(&self) -> Result<u64, String> {
+        let prime = self
+            .curve()
+            .prime()
+            .to_u64()
+            .filter(|&prime| prime < 1 << 62)
+            .ok_or_else(|| format!("Cannot compute orders over F{}", self.curve().prime()))?;
+        let bound = prime + 1 + 2 * (prime.isqrt() + 1);
+
+        if bound <= NAIVE_LIMIT {
+            return Ok(self.naive_order());
+        }
+
+        // Baby steps: j·self for j < steps, keeping the first j seen for each point.
+        let steps = bound.isqrt() + 1;
+        let mut baby = HashMap::new();
+        let mut multiple = self.curve().infinity();
+        for j in 0..steps {
+            if j > 0 && multiple.x.is_infinity() {
+                return Ok(j);
+            }
+            baby.entry(key(&multiple)).or_insert(j);
+            multiple = (multiple + *self).unwrap();
+        }
+
+        // Giant steps: -(i·steps)·self = j·self means (i·steps + j)·self is at
+        // infinity, and i·steps + j covers every candidate up to the bound.
+        let giant = -multiple;
+        let mut current = giant;
+        for i in 1..=steps {
+            if let Some(&j) = baby.get(&key(&current)) {
+                return Ok(self.reduce_to_order(i * steps + j));
+            }
+            current = (current + giant).unwrap();
+        }
+
+        Err(format!("{} has no order below {}", self, bound))
+    }
+
+    fn naive_order(&self) -> u64 {
+        let mut order = 1;
+        let mut multiple = *self;
+        while !multiple.x.is_infinity() {
+            multiple = (multiple + *self).unwrap();
+            order += 1;
+        }
+
+        order
+    }
+
+    /// The order divides any `multiple` with multiple·self at infinity, so
+    /// it is found by dividing out prime factors while that still holds.
+    fn reduce_to_order(&self, multiple: u64) -> u64 {
+        let mut order = multiple;
+        // Below 2^62 trial division never needs divisors past u32::MAX, and
+        // stops at the square root of what is left anyway.
+        for factor in prime_factors(multiple, u32::MAX).unwrap() {
+            while order.is_multiple_of(factor)
+                && (U256::from_u64(order / factor) * *self).x.is_infinity()
+            {
+                order /= factor;
+            }
+        }
+
+        order
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_orders_on_book_curve() {
+        let cases = [
+            ((47, 71), 21),
+            ((15, 86), 7),
+            ((17, 56), 42),
+            ((143, 98), 42),
+        ];
+        for ((x, y), order) in cases {
+            let point = Point::from_finite_field(x, y, 0, 7, 223).unwrap();
+            assert_eq!(point.order(), Ok(order));
+            assert!((order as u32 * point).x.is_infinity());
+        }
+    }
+
+    #[test]
+    fn point_at_infinity_has_order_one() {
+        let point = Point::from_finite_field(47, 71, 0, 7, 223).unwrap();
+        assert_eq!(point.curve().infinity().order(), Ok(1));
+    }
+
+    #[test]
+    fn small_fields_use_naive_walk() {
+        let point = Point::from_finite_field(1, 5, 0, 7, 17).unwrap();
+        assert_eq!(point.order(), Ok(9));
+        assert_eq!(point.naive_order(), 9);
+    }
+
+    #[test]
+    fn finds_orders_over_larger_fields() {
+        let point = Point::from_finite_field(2, 420807, 0, 7, 1000003).unwrap();
+        assert_eq!(point.order(), Ok(999007));
+    }
+
+    #[test]
+    fn rejects_wide_primes() {
+        let generic: Point<FieldElement> = crate::s256_point::S256Point::G.into();
+        assert!(generic.order().is_err());
+    }
+}
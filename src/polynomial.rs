@@ -0,0 +1,423 @@
+use std::ops::{Add, Mul};
+
+use crate::{field_element::GenericFieldElement, field_uint::FieldUint};
+
+/// Polynomial with coefficients in a prime field, lowest degree first.
+/// Trailing zero coefficients are dropped, so the zero polynomial is the only
+/// one whose single coefficient is zero.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Polynomial<U: FieldUint> {
+    coefficients: Vec<GenericFieldElement<U>>,
+}
+
+impl<U: FieldUint> Polynomial<U> {
+    pub fn new(coefficients: Vec<GenericFieldElement<U>>) -> Result<Self, String> {
+        let first = coefficients
+            .first()
+            .ok_or("A polynomial needs at least one coefficient")?;
+        for coefficient in &coefficients {
+            first.check_same_field(coefficient)?;
+        }
+
+        Ok(Self::trimmed(coefficients))
+    }
+
+    fn trimmed(mut coefficients: Vec<GenericFieldElement<U>>) -> Self {
+        while coefficients.len() > 1 && coefficients.last().unwrap().number.is_zero() {
+            coefficients.pop();
+        }
+
+        Polynomial { coefficients }
+    }
+
+    pub fn coefficients(&self) -> &[GenericFieldElement<U>] {
+        &self.coefficients
+    }
+
+    pub fn prime(&self) -> U {
+        self.coefficients[0].prime
+    }
+
+    /// Degree of the polynomial, with the zero polynomial given degree 0.
+    pub fn degree(&self) -> usize {
+        self.coefficients.len() - 1
+    }
+
+    fn constant(number: U, prime: U) -> GenericFieldElement<U> {
+        GenericFieldElement::new_unchecked(number, prime)
+    }
+
+    /// Evaluates the polynomial with Horner's rule. Panics if `x` is in a
+    /// different field.
+    pub fn evaluate(&self, x: GenericFieldElement<U>) -> GenericFieldElement<U> {
+        let zero = Self::constant(U::ZERO, self.prime());
+        self.coefficients
+            .iter()
+            .rev()
+            .fold(zero, |acc, &coefficient| acc * x + coefficient)
+    }
+
+    /// The unique polynomial of degree below `points.len()` passing through
+    /// every point, built from the Lagrange basis polynomials.
+    pub fn interpolate(
+        points: &[(GenericFieldElement<U>, GenericFieldElement<U>)],
+    ) -> Result<Self, String> {
+        check_points(points)?;
+        let prime = points[0].0.prime;
+        let zero = Self::constant(U::ZERO, prime);
+        let one = Self::constant(U::ONE, prime);
+
+        let mut result = Polynomial {
+            coefficients: vec![zero],
+        };
+        for (i, &(xi, yi)) in points.iter().enumerate() {
+            let mut basis = Polynomial {
+                coefficients: vec![one],
+            };
+            let mut denominator = one;
+            for (j, &(xj, _)) in points.iter().enumerate() {
+                if i != j {
+                    basis = basis * Polynomial::trimmed(vec![-xj, one]);
+                    denominator *= xi - xj;
+                }
+            }
+
+            let scale = yi / denominator;
+            result = result
+                + Polynomial::trimmed(basis.coefficients.iter().map(|&c| c * scale).collect());
+        }
+
+        Ok(result)
+    }
+}
+
+fn check_points<U: FieldUint>(
+    points: &[(GenericFieldElement<U>, GenericFieldElement<U>)],
+) -> Result<(), String> {
+    let (first, _) = points.first().ok_or("At least one point is needed")?;
+
+    for (i, (x, y)) in points.iter().enumerate() {
+        first.check_same_field(x)?;
+        first.check_same_field(y)?;
+        if points[..i].iter().any(|(other, _)| other == x) {
+            return Err(format!("Points share the same x coordinate {}", x));
+        }
+    }
+
+    Ok(())
+}
+
+/// Value at `x` of the polynomial through `points`, without building its
+/// coefficients. With `x` set to zero this recovers the constant term, which
+/// is what threshold schemes need.
+pub fn lagrange_interpolate<U: FieldUint>(
+    points: &[(GenericFieldElement<U>, GenericFieldElement<U>)],
+    x: GenericFieldElement<U>,
+) -> Result<GenericFieldElement<U>, String> {
+    check_points(points)?;
+    points[0].0.check_same_field(&x)?;
+
+    let zero = GenericFieldElement::new_unchecked(U::ZERO, x.prime);
+    let one = GenericFieldElement::new_unchecked(U::ONE, x.prime);
+    Ok(points
+        .iter()
+        .enumerate()
+        .map(|(i, &(xi, yi))| {
+            let (numerator, denominator) = points
+                .iter()
+                .enumerate()
+                .filter(|&(j, _)| i != j)
+                .fold((one, one), |(num, den), (_, &(xj, _))| {
+                    (num * (x - xj), den * (xi - xj))
+                });
+            yi * numerator / denominator
+        })
+        .fold(zero, |acc, term| acc + term))
+}
+
+/// Panics if the polynomials are over different fields.
+impl<U: FieldUint> Add for Polynomial<U> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        let (mut longer, shorter) = if self.coefficients.len() >= other.coefficients.len() {
+            (self.coefficients, other.coefficients)
+        } else {
+            (other.coefficients, self.coefficients)
+        };
+        for (a, b) in longer.iter_mut().zip(shorter) {
+            *a += b;
+        }
+
+        Polynomial::trimmed(longer)
+    }
+}
+
+/// Primitive `n`-th root of unity, for `n` a power of two dividing p - 1.
+/// A quadratic non-residue x has order divisible by the largest power of two
+/// 2^s dividing p - 1, so x^((p - 1) / 2^s) has order exactly 2^s and can be
+/// squared down to any smaller power of two. No factoring of p - 1 is needed.
+pub fn root_of_unity<U: FieldUint>(n: usize, prime: U) -> Result<GenericFieldElement<U>, String> {
+    GenericFieldElement::<U>::new(U::ONE, prime)?;
+    root_of_unity_unchecked(n, prime)
+}
+
+/// [`root_of_unity`] for a prime taken from an existing element, so it is
+/// not tested for primality again.
+fn root_of_unity_unchecked<U: FieldUint>(
+    n: usize,
+    prime: U,
+) -> Result<GenericFieldElement<U>, String> {
+    if !n.is_power_of_two() {
+        return Err(format!("{} is not a power of two", n));
+    }
+
+    let one = GenericFieldElement::new_unchecked(U::ONE, prime);
+    let order = prime - U::ONE;
+    let two_adicity = (0..U::BITS).take_while(|&i| !order.bit(i)).count() as u32;
+    let log_n = n.trailing_zeros();
+    if log_n > two_adicity {
+        return Err(format!(
+            "The field of order {} has no root of unity of order {}",
+            prime, n
+        ));
+    }
+
+    if n == 1 {
+        return Ok(one);
+    }
+
+    let non_residue = (2u32..)
+        .map(|x| GenericFieldElement::new_unchecked(U::from(x), prime))
+        .find(|x| x.legendre() == -1)
+        .unwrap();
+
+    let mut root = non_residue.pow_uint(order >> two_adicity);
+    for _ in log_n..two_adicity {
+        root *= root;
+    }
+
+    Ok(root)
+}
+
+/// In-place iterative Cooley-Tukey transform. `values.len()` must be a power
+/// of two and `root` a primitive root of unity of that order.
+fn ntt<U: FieldUint>(values: &mut [GenericFieldElement<U>], root: GenericFieldElement<U>) {
+    let n = values.len();
+
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j |= bit;
+        if i < j {
+            values.swap(i, j);
+        }
+    }
+
+    let mut length = 2;
+    while length <= n {
+        let step = root.pow_uint(U::from((n / length) as u32));
+        for chunk in values.chunks_mut(length) {
+            let mut twiddle = GenericFieldElement::new_unchecked(U::ONE, root.prime);
+            let (low, high) = chunk.split_at_mut(length / 2);
+            for (a, b) in low.iter_mut().zip(high) {
+                let t = *b * twiddle;
+                *b = *a - t;
+                *a += t;
+                twiddle *= step;
+            }
+        }
+        length <<= 1;
+    }
+}
+
+/// Products at least this long go through the NTT when the field allows it.
+const NTT_THRESHOLD: usize = 64;
+
+impl<U: FieldUint> Polynomial<U> {
+    fn mul_schoolbook(&self, other: &Self) -> Self {
+        let zero = Self::constant(U::ZERO, self.prime());
+        let mut product = vec![zero; self.coefficients.len() + other.coefficients.len() - 1];
+        for (i, &a) in self.coefficients.iter().enumerate() {
+            for (j, &b) in other.coefficients.iter().enumerate() {
+                product[i + j] += a * b;
+            }
+        }
+
+        Polynomial::trimmed(product)
+    }
+
+    /// Multiplication in O(n log n) with the number-theoretic transform. Fails
+    /// if the field has no root of unity of the power of two above the
+    /// product length.
+    pub fn mul_ntt(&self, other: &Self) -> Result<Self, String> {
+        self.coefficients[0].check_same_field(&other.coefficients[0])?;
+
+        let length = self.coefficients.len() + other.coefficients.len() - 1;
+        let size = length.next_power_of_two();
+        let root = root_of_unity_unchecked(size, self.prime())?;
+
+        let zero = Self::constant(U::ZERO, self.prime());
+        let mut a = self.coefficients.clone();
+        let mut b = other.coefficients.clone();
+        a.resize(size, zero);
+        b.resize(size, zero);
+
+        ntt(&mut a, root);
+        ntt(&mut b, root);
+        for (x, y) in a.iter_mut().zip(b) {
+            *x *= y;
+        }
+        ntt(&mut a, root.inverse());
+
+        let scale = Self::constant(U::from(size as u32), self.prime()).inverse();
+        a.truncate(length);
+        Ok(Polynomial::trimmed(
+            a.into_iter().map(|x| x * scale).collect(),
+        ))
+    }
+}
+
+/// Schoolbook multiplication for short polynomials, NTT for long ones when
+/// the field has the roots of unity for it. Panics if the polynomials are
+/// over different fields.
+impl<U: FieldUint> Mul for Polynomial<U> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        self.coefficients[0]
+            .check_same_field(&other.coefficients[0])
+            .unwrap_or_else(|e| panic!("{}", e));
+
+        if self.coefficients.len() + other.coefficients.len() > NTT_THRESHOLD {
+            if let Ok(product) = self.mul_ntt(&other) {
+                return product;
+            }
+        }
+
+        self.mul_schoolbook(&other)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    type F = GenericFieldElement<u64>;
+
+    const PRIME: u64 = 223;
+
+    fn element(number: u64) -> F {
+        F::new(number, PRIME).unwrap()
+    }
+
+    fn polynomial(coefficients: &[u64]) -> Polynomial<u64> {
+        Polynomial::new(coefficients.iter().map(|&c| element(c)).collect()).unwrap()
+    }
+
+    #[test]
+    fn trailing_zero_coefficients_are_dropped() {
+        assert_eq!(polynomial(&[1, 2, 0, 0]).degree(), 1);
+        assert_eq!(polynomial(&[0, 0]).coefficients(), &[element(0)]);
+    }
+
+    #[test]
+    fn cannot_mix_fields() {
+        assert!(Polynomial::new(vec![element(1), F::new(1u64, 7).unwrap()]).is_err());
+        assert!(Polynomial::<u64>::new(vec![]).is_err());
+    }
+
+    #[test]
+    fn evaluates_with_horner() {
+        // 3 + 2x + x^2 at 5 is 38.
+        assert_eq!(polynomial(&[3, 2, 1]).evaluate(element(5)), element(38));
+    }
+
+    #[test]
+    fn multiplies_and_adds() {
+        // (1 + x)(1 - x) = 1 - x^2
+        let product = polynomial(&[1, 1]) * polynomial(&[1, PRIME - 1]);
+        assert_eq!(product, polynomial(&[1, 0, PRIME - 1]));
+        assert_eq!(product + polynomial(&[0, 0, 1]), polynomial(&[1]));
+    }
+
+    #[test]
+    fn interpolation_recovers_polynomial() {
+        let original = polynomial(&[17, 4, 200, 9]);
+        let points: Vec<(F, F)> = [1, 2, 30, 222]
+            .iter()
+            .map(|&x| (element(x), original.evaluate(element(x))))
+            .collect();
+
+        assert_eq!(Polynomial::interpolate(&points).unwrap(), original);
+        assert_eq!(
+            lagrange_interpolate(&points, element(0)).unwrap(),
+            element(17)
+        );
+        assert_eq!(
+            lagrange_interpolate(&points, element(100)).unwrap(),
+            original.evaluate(element(100))
+        );
+    }
+
+    // 998244353 = 119 * 2^23 + 1, the usual NTT-friendly prime.
+    const NTT_PRIME: u64 = 998_244_353;
+
+    fn pseudo_random(length: usize, prime: u64, seed: u64) -> Polynomial<u64> {
+        let mut state = seed;
+        let coefficients = (0..length)
+            .map(|_| {
+                state = state.wrapping_mul(6364136223846793005).wrapping_add(1);
+                F::new((state >> 33) % prime, prime).unwrap()
+            })
+            .collect();
+        Polynomial::new(coefficients).unwrap()
+    }
+
+    #[test]
+    fn finds_roots_of_unity() {
+        for n in [1, 2, 8, 1 << 23] {
+            let root = root_of_unity(n, NTT_PRIME).unwrap();
+            assert_eq!(root.pow_uint(n as u64).number, 1);
+            if n > 1 {
+                assert_ne!(root.pow_uint(n as u64 / 2).number, 1);
+            }
+        }
+
+        assert!(root_of_unity(1 << 24, NTT_PRIME).is_err());
+        assert!(root_of_unity(3, NTT_PRIME).is_err());
+        assert!(root_of_unity(4, PRIME).is_err());
+        assert!(root_of_unity(2, PRIME).is_ok());
+    }
+
+    #[test]
+    fn ntt_multiplication_matches_schoolbook() {
+        for (a, b) in [(1, 1), (3, 5), (64, 64), (100, 37)] {
+            let x = pseudo_random(a, NTT_PRIME, a as u64);
+            let y = pseudo_random(b, NTT_PRIME, b as u64 + 1000);
+            assert_eq!(x.mul_ntt(&y).unwrap(), x.mul_schoolbook(&y));
+            assert_eq!(x.clone() * y.clone(), x.mul_schoolbook(&y));
+        }
+    }
+
+    #[test]
+    fn large_products_fall_back_when_field_lacks_roots() {
+        // 223 - 1 = 2 * 111, so only products of length 2 or less can use NTT.
+        let x = pseudo_random(50, PRIME, 1);
+        let y = pseudo_random(50, PRIME, 2);
+        assert!(x.mul_ntt(&y).is_err());
+        assert_eq!(x.clone() * y.clone(), x.mul_schoolbook(&y));
+    }
+
+    #[test]
+    fn cannot_interpolate_repeated_x_coordinates() {
+        let points = [(element(1), element(2)), (element(1), element(3))];
+        assert!(Polynomial::interpolate(&points).is_err());
+        assert!(lagrange_interpolate(&points, element(0)).is_err());
+        assert!(lagrange_interpolate::<u64>(&[], element(0)).is_err());
+    }
+}
@@ -1,9 +1,36 @@
-pub trait Pow {
-    fn pow(&self, exp: i32) -> Self;
+use crate::{
+    algebra::{multiply, Multiplicative},
+    u256::U256,
+};
+
+/// Exponentiation. Exponents default to i32, which also allows negative
+/// powers; types that need exponents like (p + 1) / 4 implement `Pow<U256>`.
+pub trait Pow<E = i32> {
+    fn pow(&self, exp: E) -> Self;
 }
 
 impl Pow for f32 {
     fn pow(&self, exp: i32) -> Self {
         self.powi(exp)
     }
-}
\ No newline at end of file
+}
+
+impl Pow<U256> for f32 {
+    fn pow(&self, exp: U256) -> Self {
+        multiply(Multiplicative(*self), exp.bits(), |i| exp.bit(i)).0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wide_exponents_match_powi() {
+        for exp in 0..20 {
+            assert_eq!(Pow::pow(&1.5f32, U256::from(exp)), 1.5f32.powi(exp as i32));
+        }
+        assert_eq!(Pow::pow(&1.0f32, U256::MAX), 1.0);
+        assert_eq!(Pow::pow(&0.5f32, U256::MAX), 0.0);
+    }
+}
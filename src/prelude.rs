@@ -0,0 +1,16 @@
+//! The types and traits needed for everyday field and curve arithmetic.
+
+pub use crate::{
+    coordinate::Coordinate,
+    curve::Curve,
+    field_element::{FieldElement, GenericFieldElement},
+    field_uint::FieldUint,
+    named_curve::{NamedCurve, Secp256k1, P256},
+    point::Point,
+    pow::Pow,
+    real_value::RealValue,
+    s256_field::S256Field,
+    s256_point::S256Point,
+    scalar::Scalar,
+    u256::U256,
+};
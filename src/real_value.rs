@@ -3,9 +3,10 @@ use std::{
     ops::{Add, Div, Mul, Sub},
 };
 
-use crate::{is_zero::IsZero, pow::Pow};
+use crate::{is_zero::IsZero, pow::Pow, u256::U256};
 
 #[derive(Debug, PartialEq, Copy, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct RealValue(f32);
 
 impl Eq for RealValue {}
@@ -70,6 +71,12 @@ impl Pow for RealValue {
     }
 }
 
+impl Pow<U256> for RealValue {
+    fn pow(&self, exp: U256) -> Self {
+        RealValue(self.0.pow(exp))
+    }
+}
+
 impl IsZero for RealValue {
     fn is_zero(&self) -> bool {
         self.0 == 0.0
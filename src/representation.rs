@@ -0,0 +1,114 @@
+//! Ways of storing a curve point. Algorithms written against
+//! [`Representation`] run unchanged on affine or Jacobian points, so
+//! comparing representations only means changing a type parameter.
+
+use crate::{
+    algebra::{multiply, Group},
+    coordinate::GraphPoint,
+    point::Point,
+    u256::U256,
+};
+
+pub trait Representation<G: GraphPoint>: Group {
+    fn from_affine(point: Point<G>) -> Self;
+    fn to_affine(self) -> Point<G>;
+}
+
+impl<G: GraphPoint> Representation<G> for Point<G> {
+    fn from_affine(point: Point<G>) -> Self {
+        point
+    }
+
+    fn to_affine(self) -> Point<G> {
+        self
+    }
+}
+
+/// Double-and-add computed in the representation `R`.
+pub fn scalar_mul<R: Representation<G>, G: GraphPoint>(
+    point: Point<G>,
+    bits: u32,
+    bit: impl Fn(u32) -> bool,
+) -> Point<G> {
+    multiply(R::from_affine(point), bits, bit).to_affine()
+}
+
+/// k1·P1 + k2·P2 + ... with a single chain of doublings shared by every
+/// term, computed in the representation `R`.
+pub fn multi_scalar_mul<R: Representation<G>, G: GraphPoint>(
+    terms: &[(U256, Point<G>)],
+) -> Result<Point<G>, String> {
+    let (_, first) = terms
+        .first()
+        .ok_or_else(|| "Cannot multiply an empty list of points".to_string())?;
+    if let Some((_, point)) = terms.iter().find(|(_, p)| p.curve() != first.curve()) {
+        return Err(format!(
+            "Points {}, {} are not on the same curve",
+            first, point
+        ));
+    }
+
+    let points: Vec<R> = terms.iter().map(|&(_, p)| R::from_affine(p)).collect();
+    let bits = terms.iter().map(|(k, _)| k.bits()).max().unwrap_or(0);
+    let mut result = points[0].identity();
+    for i in (0..bits).rev() {
+        result = result.double();
+        for ((k, _), &point) in terms.iter().zip(&points) {
+            if k.bit(i) {
+                result = result.combine(point);
+            }
+        }
+    }
+
+    Ok(result.to_affine())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{field_element::FieldElement, jacobian::JacobianPoint, real_value::RealValue};
+
+    fn generator() -> Point<FieldElement> {
+        Point::from_finite_field(47, 71, 0, 7, 223).unwrap()
+    }
+
+    #[test]
+    fn representations_agree_on_scalar_multiplication() {
+        let point = generator();
+        for k in 0..30u32 {
+            let bits = u32::BITS - k.leading_zeros();
+            let affine = scalar_mul::<Point<_>, _>(point, bits, |i| k & (1 << i) != 0);
+            let jacobian = scalar_mul::<JacobianPoint<_>, _>(point, bits, |i| k & (1 << i) != 0);
+            assert_eq!(affine, k * point);
+            assert_eq!(jacobian, affine);
+        }
+    }
+
+    #[test]
+    fn multi_scalar_multiplication_matches_sum_of_products() {
+        let p = generator();
+        let q = Point::from_finite_field(15, 86, 0, 7, 223).unwrap();
+        let terms = [
+            (U256::from(5), p),
+            (U256::from(12), q),
+            (U256::from(300), p),
+        ];
+        let expected = ((5 * p + 12 * q).unwrap() + 300 * p).unwrap();
+
+        assert_eq!(multi_scalar_mul::<Point<_>, _>(&terms), Ok(expected));
+        assert_eq!(
+            multi_scalar_mul::<JacobianPoint<_>, _>(&terms),
+            Ok(expected)
+        );
+    }
+
+    #[test]
+    fn multi_scalar_multiplication_needs_points_on_one_curve() {
+        assert!(multi_scalar_mul::<Point<FieldElement>, _>(&[]).is_err());
+        let terms = [
+            (U256::ONE, Point::new(-1, -1, 5, 7).unwrap()),
+            (U256::ONE, Point::new(0, 1, 1, 1).unwrap()),
+        ];
+        assert!(multi_scalar_mul::<JacobianPoint<RealValue>, _>(&terms).is_err());
+    }
+}
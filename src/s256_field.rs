@@ -0,0 +1,281 @@
+use std::{
+    fmt::{Display, Formatter, LowerHex, Result as FmtResult, UpperHex},
+    ops::{Add, Div, Mul, Neg, Sub},
+};
+
+use crate::{
+    field_element::FieldElement, field_uint::FieldUint, is_zero::IsZero, pow::Pow, u256::U256,
+};
+
+/// The secp256k1 field prime, 2^256 - 2^32 - 977.
+pub const P: U256 = U256::from_limbs([
+    0xFFFFFFFEFFFFFC2F,
+    0xFFFFFFFFFFFFFFFF,
+    0xFFFFFFFFFFFFFFFF,
+    0xFFFFFFFFFFFFFFFF,
+]);
+
+/// Element of the secp256k1 base field. The prime is fixed, so elements of
+/// this type can never be mixed with elements of another field.
+#[derive(Debug, PartialEq, Eq, Copy, Clone)]
+pub struct S256Field(FieldElement);
+
+impl S256Field {
+    pub fn new<N: Into<U256>>(number: N) -> Result<Self, String> {
+        // P is known to be prime, so only the range needs checking.
+        let number = number.into();
+        if number >= P {
+            return Err(format!(
+                "Number {} not in field range 0 to {}",
+                number,
+                P - U256::ONE
+            ));
+        }
+
+        Ok(S256Field(FieldElement::new_unchecked(number, P)))
+    }
+
+    /// For constants; `number` must already be below P.
+    pub(crate) const fn from_reduced(number: U256) -> Self {
+        S256Field(FieldElement { number, prime: P })
+    }
+
+    pub fn from_hex(hex: &str) -> Result<Self, String> {
+        Self::new(U256::from_hex(hex)?)
+    }
+
+    /// Parses a big-endian number of at most 32 significant bytes.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let number = <U256 as FieldUint>::from_be_bytes(bytes)
+            .ok_or_else(|| format!("{} bytes do not fit in a field element", bytes.len()))?;
+        Self::new(number)
+    }
+
+    pub fn number(&self) -> U256 {
+        self.0.number
+    }
+
+    pub fn to_bytes32(self) -> [u8; 32] {
+        self.0.to_bytes32()
+    }
+
+    pub fn pow_uint(&self, exponent: U256) -> Self {
+        S256Field(self.0.pow_uint(exponent))
+    }
+
+    pub fn inverse(&self) -> Self {
+        S256Field(self.0.inverse())
+    }
+
+    pub fn legendre(&self) -> i32 {
+        self.0.legendre()
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.0.is_square()
+    }
+
+    /// Square root, if one exists.
+    pub fn sqrt(&self) -> Option<Self> {
+        self.0.sqrt().map(S256Field)
+    }
+}
+
+impl From<S256Field> for FieldElement {
+    fn from(value: S256Field) -> Self {
+        value.0
+    }
+}
+
+impl TryFrom<FieldElement> for S256Field {
+    type Error = String;
+
+    fn try_from(value: FieldElement) -> Result<Self, Self::Error> {
+        if value.prime != P {
+            return Err(format!(
+                "{} is not an element of the secp256k1 field",
+                value
+            ));
+        }
+
+        Ok(S256Field(value))
+    }
+}
+
+/// Prints the number as 64 zero-padded hex digits, like other Bitcoin tools.
+impl Display for S256Field {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{:064x}", self.0.number)
+    }
+}
+
+impl LowerHex for S256Field {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        LowerHex::fmt(&self.0, f)
+    }
+}
+
+impl UpperHex for S256Field {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        UpperHex::fmt(&self.0, f)
+    }
+}
+
+impl Pow for S256Field {
+    fn pow(&self, exp: i32) -> Self {
+        S256Field(self.0.pow(exp))
+    }
+}
+
+impl Pow<U256> for S256Field {
+    fn pow(&self, exp: U256) -> Self {
+        S256Field(self.0.pow(exp))
+    }
+}
+
+impl Add for S256Field {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        S256Field(self.0 + other.0)
+    }
+}
+
+impl Add<i32> for S256Field {
+    type Output = Self;
+
+    fn add(self, other: i32) -> Self::Output {
+        S256Field(self.0 + other)
+    }
+}
+
+impl Sub for S256Field {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        S256Field(self.0 - other.0)
+    }
+}
+
+impl Mul for S256Field {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        S256Field(self.0 * other.0)
+    }
+}
+
+impl Mul<i32> for S256Field {
+    type Output = Self;
+
+    fn mul(self, other: i32) -> Self::Output {
+        S256Field(self.0 * other)
+    }
+}
+
+impl Div for S256Field {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self::Output {
+        S256Field(self.0 / other.0)
+    }
+}
+
+impl Neg for S256Field {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        S256Field(-self.0)
+    }
+}
+
+impl IsZero for S256Field {
+    fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GX: &str = "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+    const GY: &str = "483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8";
+
+    #[test]
+    fn cannot_create_element_outside_of_field() {
+        assert!(S256Field::new(P).is_err());
+    }
+
+    #[test]
+    fn generator_satisfies_curve_equation() {
+        let x = S256Field::from_hex(GX).unwrap();
+        let y = S256Field::from_hex(GY).unwrap();
+        assert_eq!(y.pow(2), x.pow(3) + 7);
+    }
+
+    #[test]
+    fn sqrt_recovers_root_of_square() {
+        let y = S256Field::from_hex(GY).unwrap();
+        let root = y.pow(2).sqrt().unwrap();
+        assert!(root == y || root == -y);
+    }
+
+    #[test]
+    fn sqrt_of_non_residue_is_none() {
+        let minus_one = S256Field::new(0).unwrap() + -1;
+        assert!(!minus_one.is_square());
+        assert!(minus_one.sqrt().is_none());
+    }
+
+    #[test]
+    fn fermat_inverse_matches_inverse() {
+        let x = S256Field::from_hex(GX).unwrap();
+        assert_eq!(x.pow_uint(P - U256::from(2)), x.inverse());
+    }
+
+    #[test]
+    fn dividing_by_element_is_multiplying_by_inverse() {
+        let x = S256Field::from_hex(GX).unwrap();
+        let y = S256Field::from_hex(GY).unwrap();
+        assert_eq!(x / y * y, x);
+        assert_eq!(x * x.inverse(), S256Field::new(1).unwrap());
+    }
+
+    #[test]
+    fn cannot_convert_element_of_other_field() {
+        let element = FieldElement::new(1, 223).unwrap();
+        assert!(S256Field::try_from(element).is_err());
+    }
+
+    #[test]
+    fn displays_as_zero_padded_hex() {
+        assert_eq!(S256Field::from_hex(GX).unwrap().to_string(), GX);
+        assert_eq!(
+            S256Field::new(1).unwrap().to_string(),
+            format!("{:064x}", 1)
+        );
+        assert_eq!(format!("{:x}", S256Field::new(255).unwrap()), "ff");
+        assert_eq!(
+            format!("{:X}", S256Field::from_hex(GX).unwrap()),
+            GX.to_uppercase()
+        );
+    }
+
+    #[test]
+    fn bytes_roundtrip() {
+        let x = S256Field::from_hex(GX).unwrap();
+        let bytes = x.to_bytes32();
+        assert_eq!(bytes[..4], [0x79, 0xbe, 0x66, 0x7e]);
+        assert_eq!(S256Field::from_bytes(&bytes), Ok(x));
+        assert!(S256Field::from_bytes(&P.to_be_bytes()).is_err());
+    }
+
+    #[test]
+    fn converts_to_and_from_field_element() {
+        let x = S256Field::from_hex(GX).unwrap();
+        let element: FieldElement = x.into();
+        assert_eq!(element.prime, P);
+        assert_eq!(S256Field::try_from(element), Ok(x));
+    }
+}
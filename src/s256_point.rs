@@ -0,0 +1,619 @@
+use std::{
+    fmt::{Display, Formatter, Result as FmtResult},
+    ops::{Add, Mul, MulAssign, Neg, Sub},
+};
+
+use crate::{
+    coordinate::Coordinate,
+    curve::{Curve, Parity},
+    field_element::FieldElement,
+    jacobian::{double_scalar_mul, wnaf_mul},
+    point::Point,
+    s256_field::{S256Field, P},
+    scalar::Scalar,
+    u256::U256,
+};
+
+mod generator_table;
+
+/// Order of the secp256k1 generator.
+pub const N: U256 = U256::from_limbs([
+    0xBFD25E8CD0364141,
+    0xBAAEDCE6AF48A03B,
+    0xFFFFFFFFFFFFFFFE,
+    0xFFFFFFFFFFFFFFFF,
+]);
+
+pub const GX: U256 = U256::from_limbs([
+    0x59F2815B16F81798,
+    0x029BFCDB2DCE28D9,
+    0x55A06295CE870B07,
+    0x79BE667EF9DCBBAC,
+]);
+
+pub const GY: U256 = U256::from_limbs([
+    0x9C47D08FFB10D4B8,
+    0xFD17B448A6855419,
+    0x5DA4FBFC0E1108A8,
+    0x483ADA7726A3C465,
+]);
+
+/// secp256k1 is y^2 = x^3 + 7.
+pub const CURVE: Curve<S256Field> = Curve::from_parameters(
+    S256Field::from_reduced(U256::ZERO),
+    S256Field::from_reduced(U256::from_u64(7)),
+);
+
+/// Point on secp256k1. Scalars are taken modulo N.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct S256Point(Point<S256Field>);
+
+impl S256Point {
+    pub const G: S256Point = S256Point(Point::from_parts(
+        Coordinate::Value(S256Field::from_reduced(GX)),
+        Coordinate::Value(S256Field::from_reduced(GY)),
+        CURVE,
+    ));
+
+    pub fn new(x: S256Field, y: S256Field) -> Result<Self, String> {
+        CURVE.point(x, y).map(S256Point)
+    }
+
+    pub fn infinity() -> Self {
+        S256Point(CURVE.infinity())
+    }
+
+    pub fn is_infinity(&self) -> bool {
+        self.0.x.is_infinity()
+    }
+
+    pub fn x(&self) -> Option<S256Field> {
+        match self.0.x {
+            Coordinate::Value(x) => Some(x),
+            Coordinate::Infinity => None,
+        }
+    }
+
+    pub fn y(&self) -> Option<S256Field> {
+        match self.0.y {
+            Coordinate::Value(y) => Some(y),
+            Coordinate::Infinity => None,
+        }
+    }
+
+    /// The point with x-coordinate `x` and y of the given parity, as stored
+    /// by compressed SEC encodings.
+    pub fn from_x(x: U256, parity: Parity) -> Result<Self, String> {
+        let x = S256Field::new(x)?;
+        let y = generic_curve().solve_y(x.into(), parity)?;
+
+        S256Point::new(x, y.try_into()?)
+    }
+
+    /// The point with x-coordinate `x` and even y, as BIP 340 decodes x-only
+    /// public keys.
+    pub fn lift_x(x: U256) -> Result<Self, String> {
+        S256Point::from_x(x, Parity::Even)
+    }
+
+    /// False for the point at infinity, which has no y-coordinate.
+    pub fn has_even_y(&self) -> bool {
+        self.y().is_some_and(|y| !y.number().is_odd())
+    }
+
+    /// Self or its negation, whichever has even y. BIP 340 keys and nonces
+    /// are normalized this way so that x alone identifies them.
+    pub fn with_even_y(self) -> Self {
+        if self.has_even_y() || self.is_infinity() {
+            self
+        } else {
+            -self
+        }
+    }
+
+    /// BIP 340 x-only encoding: the 32 bytes of x. None for the point at
+    /// infinity.
+    pub fn x_only_bytes(&self) -> Option<[u8; 32]> {
+        self.x().map(|x| x.to_bytes32())
+    }
+
+    /// SEC 1 encoding: 0x04 followed by x and y, or when compressed 0x02 or
+    /// 0x03 (for even or odd y) followed by x. The point at infinity is a
+    /// single 0x00 byte.
+    pub fn sec(&self, compressed: bool) -> Vec<u8> {
+        let (Some(x), Some(y)) = (self.x(), self.y()) else {
+            return vec![0x00];
+        };
+
+        let mut bytes = Vec::with_capacity(65);
+        if compressed {
+            bytes.push(if y.number().is_odd() { 0x03 } else { 0x02 });
+            bytes.extend(x.to_bytes32());
+        } else {
+            bytes.push(0x04);
+            bytes.extend(x.to_bytes32());
+            bytes.extend(y.to_bytes32());
+        }
+
+        bytes
+    }
+
+    /// Parses a SEC 1 encoded point, recovering y from the curve equation
+    /// for the compressed forms.
+    pub fn parse(bytes: &[u8]) -> Result<Self, String> {
+        match (bytes.first(), bytes.len()) {
+            (Some(0x00), 1) => Ok(S256Point::infinity()),
+            (Some(0x04), 65) => S256Point::new(
+                S256Field::from_bytes(&bytes[1..33])?,
+                S256Field::from_bytes(&bytes[33..])?,
+            ),
+            (Some(&prefix @ (0x02 | 0x03)), 33) => S256Point::from_x(
+                U256::from_be_bytes(bytes[1..].try_into().unwrap()),
+                if prefix == 0x03 {
+                    Parity::Odd
+                } else {
+                    Parity::Even
+                },
+            ),
+            (Some(prefix), length) => Err(format!(
+                "Invalid SEC encoding: prefix {:#04x} with {} bytes",
+                prefix, length
+            )),
+            (None, _) => Err("Invalid SEC encoding: no bytes".to_string()),
+        }
+    }
+
+    /// u·G + v·P in a single pass, as needed by signature verification.
+    pub fn mul_add_generator(u: U256, v: U256, point: S256Point) -> S256Point {
+        S256Point(double_scalar_mul(
+            u.div_rem(N).1,
+            S256Point::G.0,
+            v.div_rem(N).1,
+            point.0,
+        ))
+    }
+
+    /// Checks an ECDSA signature (r, s) of the message hash `z` against this
+    /// public key.
+    pub fn verify(&self, z: U256, r: U256, s: U256) -> bool {
+        if r.is_zero() || r >= N || s.is_zero() || s >= N {
+            return false;
+        }
+
+        let s_inverse = Scalar::reduce(s).invert().unwrap();
+        let u = Scalar::reduce(z) * s_inverse;
+        let v = Scalar::reduce(r) * s_inverse;
+        let total = S256Point::mul_add_generator(u.value(), v.value(), *self);
+
+        total.x().is_some_and(|x| x.number() % N == r)
+    }
+}
+
+/// [`CURVE`] over the generic field, without checking again that P is prime.
+fn generic_curve() -> Curve<FieldElement> {
+    Curve::new(FieldElement::from(CURVE.a()), FieldElement::from(CURVE.b()))
+}
+
+impl From<S256Point> for Point<FieldElement> {
+    fn from(value: S256Point) -> Self {
+        let coordinate = |c: Coordinate<S256Field>| match c {
+            Coordinate::Value(v) => Coordinate::Value(FieldElement::from(v)),
+            Coordinate::Infinity => Coordinate::Infinity,
+        };
+        Point::on_curve(
+            coordinate(value.0.x),
+            coordinate(value.0.y),
+            generic_curve(),
+        )
+        .unwrap()
+    }
+}
+
+impl TryFrom<Point<FieldElement>> for S256Point {
+    type Error = String;
+
+    fn try_from(value: Point<FieldElement>) -> Result<Self, Self::Error> {
+        let curve = value.curve();
+        if curve.prime() != P || curve.a().number != U256::ZERO || curve.b().number != U256::from(7)
+        {
+            return Err(format!("{} is not a point on secp256k1", value));
+        }
+
+        match (value.x, value.y) {
+            (Coordinate::Value(x), Coordinate::Value(y)) => {
+                S256Point::new(S256Field::try_from(x)?, S256Field::try_from(y)?)
+            }
+            _ => Ok(S256Point::infinity()),
+        }
+    }
+}
+
+impl TryFrom<(S256Field, S256Field)> for S256Point {
+    type Error = String;
+
+    fn try_from((x, y): (S256Field, S256Field)) -> Result<Self, Self::Error> {
+        S256Point::new(x, y)
+    }
+}
+
+/// For coordinates parsed as plain integers, which also need a range check.
+impl TryFrom<(U256, U256)> for S256Point {
+    type Error = String;
+
+    fn try_from((x, y): (U256, U256)) -> Result<Self, Self::Error> {
+        S256Point::new(S256Field::new(x)?, S256Field::new(y)?)
+    }
+}
+
+impl Display for S256Point {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        match (self.x(), self.y()) {
+            (Some(x), Some(y)) => write!(f, "S256Point({}, {})", x, y),
+            _ => write!(f, "S256Point(infinity)"),
+        }
+    }
+}
+
+impl Add for S256Point {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        // Both points are on secp256k1, so the curves always match.
+        S256Point((self.0 + other.0).unwrap())
+    }
+}
+
+impl Neg for S256Point {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        S256Point(-self.0)
+    }
+}
+
+impl Sub for S256Point {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        self + -other
+    }
+}
+
+impl Mul<S256Point> for U256 {
+    type Output = S256Point;
+
+    fn mul(self, point: S256Point) -> Self::Output {
+        let coefficient = self.div_rem(N).1;
+        if point == S256Point::G {
+            return S256Point(generator_table::mul_generator(coefficient));
+        }
+
+        S256Point(wnaf_mul(point.0, coefficient.bits(), |i| {
+            coefficient.bit(i)
+        }))
+    }
+}
+
+impl<'a> Mul<&'a S256Point> for U256 {
+    type Output = S256Point;
+
+    fn mul(self, point: &'a S256Point) -> Self::Output {
+        self * *point
+    }
+}
+
+impl MulAssign<U256> for S256Point {
+    fn mul_assign(&mut self, coefficient: U256) {
+        *self = coefficient * *self;
+    }
+}
+
+impl MulAssign<Scalar> for S256Point {
+    fn mul_assign(&mut self, coefficient: Scalar) {
+        *self = coefficient * *self;
+    }
+}
+
+impl Mul<S256Point> for u32 {
+    type Output = S256Point;
+
+    fn mul(self, point: S256Point) -> Self::Output {
+        U256::from(self) * point
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn hex(value: &str) -> U256 {
+        U256::from_hex(value).unwrap()
+    }
+
+    fn point(x: &str, y: &str) -> S256Point {
+        S256Point::new(
+            S256Field::from_hex(x).unwrap(),
+            S256Field::from_hex(y).unwrap(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn generator_is_on_curve() {
+        assert_eq!(
+            S256Point::new(S256Field::new(GX).unwrap(), S256Field::new(GY).unwrap()),
+            Ok(S256Point::G)
+        );
+    }
+
+    #[test]
+    fn order_of_generator_is_n() {
+        assert!((N * S256Point::G).is_infinity());
+        assert!((N - U256::ONE) * S256Point::G + S256Point::G == S256Point::infinity());
+    }
+
+    #[test]
+    fn multiplies_generator() {
+        let cases = [
+            (
+                U256::from(7),
+                "5cbdf0646e5db4eaa398f365f2ea7a0e3d419b7e0330e39ce92bddedcac4f9bc",
+                "6aebca40ba255960a3178d6d861a54dba813d0b813fde7b5a5082628087264da",
+            ),
+            (
+                U256::from(1485),
+                "c982196a7466fbbbb0e27a940b6af926c1a74d5ad07128c82824a11b5398afda",
+                "7a91f9eae64438afb9ce6448a1c133db2d8fb9254e4546b6f001637d50901f55",
+            ),
+            (
+                U256::ONE << 128,
+                "8f68b9d2f63b5f339239c1ad981f162ee88c5678723ea3351b7b444c9ec4c0da",
+                "662a9f2dba063986de1d90c2b6be215dbbea2cfe95510bfdf23cbf79501fff82",
+            ),
+            (
+                (U256::ONE << 240) + (U256::ONE << 31),
+                "9577ff57c8234558f293df502ca4f09cbc65a6572c842b39b366f21717945116",
+                "10b49c67fa9365ad7b90dab070be339a1daf9052373ec30ffae4f72d5e66d053",
+            ),
+        ];
+
+        for (secret, x, y) in cases {
+            assert_eq!(secret * S256Point::G, point(x, y));
+        }
+        assert_eq!(7 * S256Point::G, U256::from(7) * S256Point::G);
+        assert_eq!((N + U256::from(7)) * S256Point::G, 7 * S256Point::G);
+    }
+
+    #[test]
+    fn mul_add_generator_matches_separate_products() {
+        let point = U256::from(1485) * S256Point::G;
+        let u = hex("f0e1d2c3b4a5968778695a4b3c2d1e0f00112233445566778899aabbccddeeff");
+        let v = N - U256::from(2);
+        assert_eq!(
+            S256Point::mul_add_generator(u, v, point),
+            u * S256Point::G + v * point
+        );
+        assert_eq!(
+            S256Point::mul_add_generator(U256::from(1485), N - U256::ONE, point),
+            S256Point::infinity()
+        );
+    }
+
+    #[test]
+    fn multiplies_by_full_width_private_key() {
+        let secret = hex("8b387de39861728c92ec9f589c303b1038ff60eb3963b12cd212263a1d1e0f00");
+        let expected = point(
+            "028d003eab2e428d11983f3e97c3fa0addf3b42740df0d211795ffb3be2f6c52",
+            "0ae987b9ec6ea159c78cb2a937ed89096fb218d9e7594f02b547526d8cd309e2",
+        );
+        assert_eq!(secret * S256Point::G, expected);
+        let generator = &S256Point::G;
+        assert_eq!(secret * generator, expected);
+        assert_eq!(Scalar::reduce(secret) * S256Point::G, expected);
+
+        let mut public = S256Point::G;
+        public *= Scalar::reduce(secret);
+        assert_eq!(public, expected);
+        public *= N + U256::from(2);
+        assert_eq!(public, expected + expected);
+    }
+
+    fn bytes(hex: &str) -> Vec<u8> {
+        (0..hex.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&hex[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn serializes_to_sec() {
+        let uncompressed = [
+            (
+                U256::from(5000),
+                concat!(
+                    "04",
+                    "ffe558e388852f0120e46af2d1b370f85854a8eb0841811ece0e3e03d282d57c",
+                    "315dc72890a4f10a1481c031b03b351b0dc79901ca18a00cf009dbdb157a1d10",
+                ),
+            ),
+            (
+                U256::from_u64(2018u64.pow(5)),
+                concat!(
+                    "04",
+                    "027f3da1918455e03c46f659266a1bb5204e959db7364d2f473bdf8f0a13cc9d",
+                    "ff87647fd023c13b4a4994f17691895806e1b40b57f4fd22581a4f46851f3b06",
+                ),
+            ),
+            (
+                U256::from_u64(0xdeadbeef12345),
+                concat!(
+                    "04",
+                    "d90cd625ee87dd38656dd95cf79f65f60f7273b67d3096e68bd81e4f5342691f",
+                    "842efa762fd59961d0e99803c61edba8b3e3f7dc3a341836f97733aebf987121",
+                ),
+            ),
+        ];
+        let compressed = [
+            (
+                U256::from(5001),
+                "0357a4f368868a8a6d572991e484e664810ff14c05c0fa023275251151fe0e53d1",
+            ),
+            (
+                U256::from_u64(2019u64.pow(5)),
+                "02933ec2d2b111b92737ec12f1c5d20f3233a0ad21cd8b36d0bca7a0cfa5cb8701",
+            ),
+            (
+                U256::from_u64(0xdeadbeef54321),
+                "0296be5b1292f6c856b3c5654e886fc13511462059089cdf9c479623bfcbe77690",
+            ),
+        ];
+
+        for (secret, sec) in uncompressed {
+            assert_eq!((secret * S256Point::G).sec(false), bytes(sec));
+        }
+        for (secret, sec) in compressed {
+            assert_eq!((secret * S256Point::G).sec(true), bytes(sec));
+        }
+        assert_eq!(S256Point::infinity().sec(true), [0x00]);
+    }
+
+    #[test]
+    fn parses_sec() {
+        for secret in [1u32, 2, 5001, 1485] {
+            let point = secret * S256Point::G;
+            assert_eq!(S256Point::parse(&point.sec(false)), Ok(point));
+            assert_eq!(S256Point::parse(&point.sec(true)), Ok(point));
+            assert_eq!(S256Point::parse(&(-point).sec(true)), Ok(-point));
+        }
+        assert_eq!(S256Point::parse(&[0x00]), Ok(S256Point::infinity()));
+    }
+
+    #[test]
+    fn rejects_invalid_sec() {
+        let sec = S256Point::G.sec(false);
+        assert!(S256Point::parse(&[]).is_err());
+        assert!(S256Point::parse(&sec[..33]).is_err());
+        assert!(S256Point::parse(&[&[0x05], &sec[1..]].concat()).is_err());
+
+        let mut off_curve = sec.clone();
+        off_curve[64] ^= 1;
+        assert!(S256Point::parse(&off_curve).is_err());
+
+        // x = 5 is not the x-coordinate of any point: 5^3 + 7 is not a square.
+        let mut compressed = vec![0x02];
+        compressed.extend(U256::from(5).to_be_bytes());
+        assert!(S256Point::parse(&compressed).is_err());
+    }
+
+    #[test]
+    fn lifts_bip340_public_keys() {
+        // Secret and public keys of the BIP 340 test vectors 0 to 3.
+        let cases = [
+            (
+                "0000000000000000000000000000000000000000000000000000000000000003",
+                "f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9",
+            ),
+            (
+                "b7e151628aed2a6abf7158809cf4f3c762e7160f38b4da56a784d9045190cfef",
+                "dff1d77f2a671c5f36183726db2341be58feae1da2deced843240f7b502ba659",
+            ),
+            (
+                "c90fdaa22168c234c4c6628b80dc1cd129024e088a67cc74020bbea63b14e5c9",
+                "dd308afec5777e13121fa72b9cc1b7cc0139715309b086c960e18fd969774eb8",
+            ),
+            (
+                "0b432b2677937381aef05bb02a66ecd012773062cf3fa2549e44f58ed2401710",
+                "25d1dff95105f5253c4022f628a996ad3a0d95fbf21d468a1b33f8c160d8f517",
+            ),
+        ];
+
+        for (secret, public) in cases {
+            let point = hex(secret) * S256Point::G;
+            let lifted = S256Point::lift_x(hex(public)).unwrap();
+            assert_eq!(
+                point.x_only_bytes(),
+                Some(bytes(public).try_into().unwrap())
+            );
+            assert!(lifted.has_even_y());
+            assert_eq!(lifted, point.with_even_y());
+        }
+        // Vector 3 is the one whose key has odd y.
+        assert!(!(hex(cases[3].0) * S256Point::G).has_even_y());
+    }
+
+    #[test]
+    fn lift_x_rejects_invalid_coordinates() {
+        // Vector 5: not the x-coordinate of a point on the curve.
+        let not_on_curve = hex("eefdea4cdb677750a420fee807eacf21eb9898ae79b9768766e4faa04a2d4a34");
+        assert!(S256Point::lift_x(not_on_curve).is_err());
+        // Vector 14: exceeds the field size.
+        assert!(S256Point::lift_x(P + U256::ONE).is_err());
+        assert_eq!(S256Point::infinity().x_only_bytes(), None);
+        assert!(!S256Point::infinity().has_even_y());
+    }
+
+    #[test]
+    fn from_x_picks_the_requested_root() {
+        assert_eq!(S256Point::from_x(GX, Parity::Even).unwrap(), S256Point::G);
+        assert_eq!(S256Point::from_x(GX, Parity::Odd).unwrap(), -S256Point::G);
+    }
+
+    #[test]
+    fn try_from_coordinates_validates_them() {
+        assert_eq!(S256Point::try_from((GX, GY)), Ok(S256Point::G));
+        assert!(S256Point::try_from((GX, GY + U256::ONE)).is_err());
+        assert!(S256Point::try_from((P, GY)).is_err());
+
+        let x = S256Field::new(GX).unwrap();
+        let y = S256Field::new(GY).unwrap();
+        assert_eq!(S256Point::try_from((x, y)), Ok(S256Point::G));
+        assert!(S256Point::try_from((y, x)).is_err());
+    }
+
+    #[test]
+    fn subtracts_points() {
+        let seven = 7 * S256Point::G;
+        assert_eq!(seven - S256Point::G, 6 * S256Point::G);
+        assert_eq!(-seven, (N - U256::from(7)) * S256Point::G);
+        assert_eq!(seven - seven, S256Point::infinity());
+    }
+
+    #[test]
+    fn verifies_signatures() {
+        let public = point(
+            "887387e452b8eacc4acfde10d9aaf7f6d9a0f975aabb10d006e4da568744d06c",
+            "61de6d95231cd89026e286df3b6ae4a894a3378e393e93a0f45b666329a0ae34",
+        );
+        let z = hex("ec208baa0fc1c19f708a9ca96fdeff3ac3f230bb4a7ba4aede4942ad003c0f60");
+        let r = hex("ac8d1c87e51d0d441be8b3dd5b05c8795b48875dffe00b7ffcfac23010d3a395");
+        let s = hex("68342ceff8935ededd102dd876ffd6ba72d6a427a3edb13d26eb0781cb423c4");
+        assert!(public.verify(z, r, s));
+
+        let z = hex("7c076ff316692a3d7eb3c3bb0f8b1488cf72e1afcd929e29307032997a838a3d");
+        let r = hex("eff69ef2b1bd93a66ed5219add4fb51e11a840f404876325a1e8ffe0529a2c");
+        let s = hex("c7207fee197d27c618aea621406f6bf5ef6fca38681d82b2f06fddbdce6feab6");
+        assert!(public.verify(z, r, s));
+        assert!(!public.verify(z + U256::ONE, r, s));
+        assert!(!public.verify(z, r, U256::ZERO));
+        assert!(!S256Point::G.verify(z, r, s));
+    }
+
+    #[test]
+    fn converts_to_and_from_generic_point() {
+        let generic: Point<FieldElement> = S256Point::G.into();
+        assert_eq!(generic.curve().prime(), P);
+        assert_eq!(S256Point::try_from(generic), Ok(S256Point::G));
+
+        let other = Point::from_finite_field(47, 71, 0, 7, 223).unwrap();
+        assert!(S256Point::try_from(other).is_err());
+    }
+
+    #[test]
+    fn displays_coordinates_as_hex() {
+        assert_eq!(
+            S256Point::G.to_string(),
+            format!("S256Point({:064x}, {:064x})", GX, GY)
+        );
+        assert_eq!(S256Point::infinity().to_string(), "S256Point(infinity)");
+    }
+}
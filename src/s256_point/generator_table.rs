@@ -0,0 +1,95 @@
+//! Fixed-base multiplication by G. The scalar is split into 4-bit windows and
+//! every possible value of every window is precomputed, so k·G is at most 64
+//! additions and no doublings.
+
+use std::sync::OnceLock;
+
+use crate::{jacobian::JacobianPoint, point::Point, s256_field::S256Field, u256::U256};
+
+use super::{S256Point, CURVE};
+
+const WINDOW_BITS: u32 = 4;
+const WINDOWS: usize = (256 / WINDOW_BITS) as usize;
+const ENTRIES: usize = (1 << WINDOW_BITS) - 1;
+
+/// `table()[i][j]` is (j + 1)·16^i·G.
+type Table = Vec<[Point<S256Field>; ENTRIES]>;
+
+fn table() -> &'static Table {
+    static TABLE: OnceLock<Table> = OnceLock::new();
+    TABLE.get_or_init(|| {
+        let mut multiples = Vec::with_capacity(WINDOWS * ENTRIES);
+        let mut base = JacobianPoint::from(S256Point::G.0);
+        for _ in 0..WINDOWS {
+            let mut multiple = base;
+            for _ in 0..ENTRIES {
+                multiples.push(multiple);
+                multiple = multiple + base;
+            }
+            // After the loop `multiple` is 16·base.
+            base = multiple;
+        }
+
+        // One shared inversion instead of one per entry.
+        JacobianPoint::to_affine_batch(&multiples)
+            .chunks_exact(ENTRIES)
+            .map(|row| row.try_into().unwrap())
+            .collect()
+    })
+}
+
+/// The coefficient must already be reduced modulo N.
+pub(super) fn mul_generator(coefficient: U256) -> Point<S256Field> {
+    let table = table();
+    let mut result = JacobianPoint::infinity(CURVE);
+    for (i, row) in table.iter().enumerate() {
+        let start = i as u32 * WINDOW_BITS;
+        let digit = (0..WINDOW_BITS).fold(0, |digit, j| {
+            digit | (coefficient.bit(start + j) as usize) << j
+        });
+        if digit != 0 {
+            result = result + JacobianPoint::from(row[digit - 1]);
+        }
+    }
+
+    result.to_affine()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{jacobian::wnaf_mul, s256_point::N};
+
+    #[test]
+    fn table_rows_are_powers_of_sixteen() {
+        // k·S256Point::G reads this table, so compare against wNAF instead.
+        let multiple = |k: U256| wnaf_mul(S256Point::G.0, k.bits(), |i| k.bit(i));
+        let table = table();
+        assert_eq!(table[0][0], S256Point::G.0);
+        assert_eq!(table[0][14], multiple(U256::from(15)));
+        assert_eq!(table[1][0], multiple(U256::from(16)));
+        assert_eq!(table[2][2], multiple(U256::from(3 * 256)));
+        assert_eq!(
+            table[WINDOWS - 1][ENTRIES - 1],
+            multiple(U256::from(15) << (WINDOW_BITS * (WINDOWS as u32 - 1)))
+        );
+    }
+
+    #[test]
+    fn matches_generic_multiplication() {
+        let scalars = [
+            U256::ZERO,
+            U256::ONE,
+            U256::from(0xdeadbeef),
+            U256::from_u128(u128::MAX),
+            N - U256::ONE,
+            U256::from_hex("f0e1d2c3b4a5968778695a4b3c2d1e0f00112233445566778899aabbccddeeff")
+                .unwrap(),
+        ];
+
+        for k in scalars {
+            let expected = wnaf_mul(S256Point::G.0, k.bits(), |i| k.bit(i));
+            assert_eq!(mul_generator(k), expected, "{}", k);
+        }
+    }
+}
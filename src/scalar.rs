@@ -0,0 +1,252 @@
+use std::{
+    fmt::{Display, Formatter, LowerHex, Result as FmtResult, UpperHex},
+    ops::{Add, Mul, Neg, Sub},
+};
+
+use crate::{
+    algebra::{multiply, Group, Monoid, Multiplicative},
+    field_element::FieldElement,
+    field_uint::FieldUint,
+    is_zero::IsZero,
+    s256_point::{S256Point, N},
+    u256::U256,
+};
+
+/// Integer modulo N, the order of the secp256k1 group. Private keys, nonces
+/// and signature values live here, while coordinates live in
+/// [`crate::s256_field::S256Field`]; keeping them apart stops the two moduli
+/// from being mixed up.
+#[derive(Debug, PartialEq, Eq, Hash, Copy, Clone, Default)]
+pub struct Scalar(U256);
+
+impl Scalar {
+    pub const ZERO: Scalar = Scalar(U256::ZERO);
+    pub const ONE: Scalar = Scalar(U256::ONE);
+
+    pub fn new<T: Into<U256>>(value: T) -> Result<Self, String> {
+        let value = value.into();
+        if value >= N {
+            return Err(format!("Scalar {} is not below the group order", value));
+        }
+
+        Ok(Scalar(value))
+    }
+
+    /// Takes any 256-bit value modulo N, as is done for message hashes.
+    pub fn reduce(value: U256) -> Self {
+        Scalar(value % N)
+    }
+
+    /// Parses a big-endian number of at most 32 significant bytes and
+    /// reduces it modulo N.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let value = <U256 as FieldUint>::from_be_bytes(bytes)
+            .ok_or_else(|| format!("{} bytes do not fit in a scalar", bytes.len()))?;
+        Ok(Self::reduce(value))
+    }
+
+    pub fn value(&self) -> U256 {
+        self.0
+    }
+
+    pub fn to_bytes32(self) -> [u8; 32] {
+        self.0.to_be_bytes()
+    }
+
+    pub fn pow(&self, exponent: U256) -> Self {
+        multiply(Multiplicative(*self), exponent.bits(), |i| exponent.bit(i)).0
+    }
+
+    /// Multiplicative inverse, or None for zero.
+    pub fn invert(&self) -> Option<Self> {
+        if self.0.is_zero() {
+            return None;
+        }
+
+        self.0.inv_mod(N).map(Scalar)
+    }
+}
+
+impl Monoid for Multiplicative<Scalar> {
+    fn identity(&self) -> Self {
+        Multiplicative(Scalar::ONE)
+    }
+
+    fn combine(self, other: Self) -> Self {
+        Multiplicative(self.0 * other.0)
+    }
+}
+
+impl Monoid for Scalar {
+    fn identity(&self) -> Self {
+        Scalar::ZERO
+    }
+
+    fn combine(self, other: Self) -> Self {
+        self + other
+    }
+}
+
+impl Group for Scalar {
+    fn inverse(self) -> Self {
+        -self
+    }
+}
+
+impl From<u32> for Scalar {
+    fn from(value: u32) -> Self {
+        Scalar(U256::from(value))
+    }
+}
+
+impl From<Scalar> for U256 {
+    fn from(value: Scalar) -> Self {
+        value.0
+    }
+}
+
+/// The same integer in the prime field of order N, for generic code such as
+/// [`crate::shamir`].
+impl From<Scalar> for FieldElement {
+    fn from(value: Scalar) -> Self {
+        FieldElement::new_unchecked(value.0, N)
+    }
+}
+
+impl TryFrom<FieldElement> for Scalar {
+    type Error = String;
+
+    fn try_from(value: FieldElement) -> Result<Self, Self::Error> {
+        if value.prime != N {
+            return Err(format!(
+                "{} is not an integer modulo the secp256k1 group order",
+                value
+            ));
+        }
+
+        Ok(Scalar(value.number))
+    }
+}
+
+impl Display for Scalar {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "{:064x}", self.0)
+    }
+}
+
+impl LowerHex for Scalar {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        LowerHex::fmt(&self.0, f)
+    }
+}
+
+impl UpperHex for Scalar {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        UpperHex::fmt(&self.0, f)
+    }
+}
+
+impl Add for Scalar {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        Scalar(self.0.add_mod(other.0, N))
+    }
+}
+
+impl Sub for Scalar {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        Scalar(self.0.sub_mod(other.0, N))
+    }
+}
+
+impl Mul for Scalar {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        Scalar(self.0.mul_mod(other.0, N))
+    }
+}
+
+impl Neg for Scalar {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Scalar(U256::ZERO.sub_mod(self.0, N))
+    }
+}
+
+impl Mul<S256Point> for Scalar {
+    type Output = S256Point;
+
+    fn mul(self, point: S256Point) -> Self::Output {
+        self.0 * point
+    }
+}
+
+impl IsZero for Scalar {
+    fn is_zero(&self) -> bool {
+        self.0.is_zero()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cannot_create_scalar_outside_of_group_order() {
+        assert!(Scalar::new(N).is_err());
+        assert!(Scalar::new(N - U256::ONE).is_ok());
+    }
+
+    #[test]
+    fn arithmetic_wraps_around_group_order() {
+        let largest = Scalar::new(N - U256::ONE).unwrap();
+        assert_eq!(largest + Scalar::ONE, Scalar::ZERO);
+        assert_eq!(Scalar::ZERO - Scalar::ONE, largest);
+        assert_eq!(-Scalar::ONE, largest);
+        assert_eq!(largest * largest, Scalar::ONE);
+    }
+
+    #[test]
+    fn pow_matches_fermat() {
+        let value = Scalar::from(1485);
+        assert_eq!(value.pow(U256::ZERO), Scalar::ONE);
+        assert_eq!(value.pow(U256::from(3)), value * value * value);
+        assert_eq!(value.pow(N - U256::from(2)), value.invert().unwrap());
+    }
+
+    #[test]
+    fn inverts_non_zero_scalars() {
+        let value = Scalar::from(1485);
+        assert_eq!(value * value.invert().unwrap(), Scalar::ONE);
+        assert_eq!(Scalar::ZERO.invert(), None);
+    }
+
+    #[test]
+    fn from_bytes_reduces_modulo_group_order() {
+        assert_eq!(
+            Scalar::from_bytes(&[0xff; 32]).unwrap().value(),
+            U256::MAX - N
+        );
+        assert_eq!(Scalar::from_bytes(&[7]).unwrap(), Scalar::from(7));
+        assert!(Scalar::from_bytes(&[1; 33]).is_err());
+        assert_eq!(
+            Scalar::from_bytes(&Scalar::from(7).to_bytes32()).unwrap(),
+            Scalar::from(7)
+        );
+    }
+
+    #[test]
+    fn multiplies_points() {
+        let secret = Scalar::from(7) * Scalar::from(1485);
+        assert_eq!(secret * S256Point::G, U256::from(7 * 1485) * S256Point::G);
+        assert_eq!(
+            (-Scalar::ONE) * S256Point::G + S256Point::G,
+            S256Point::infinity()
+        );
+    }
+}
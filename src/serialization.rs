@@ -0,0 +1,242 @@
+//! serde support. Integers are written as 64-digit big-endian hex strings in
+//! human-readable formats and as 32 raw bytes otherwise, so fixtures do not
+//! depend on the internal limb layout. Deserialization goes through the
+//! regular constructors, so out-of-range elements and off-curve points are
+//! rejected.
+
+use std::fmt::{Formatter, Result as FmtResult};
+
+use serde::{
+    de::{Error, Visitor},
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{
+    coordinate::{Coordinate, GraphPoint},
+    field_element::GenericFieldElement,
+    field_uint::FieldUint,
+    fp::Fp,
+    point::Point,
+    s256_field::S256Field,
+    u256::U256,
+};
+
+impl Serialize for U256 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let bytes = self.to_be_bytes();
+        if serializer.is_human_readable() {
+            let hex: String = bytes.iter().map(|b| format!("{:02x}", b)).collect();
+            serializer.serialize_str(&hex)
+        } else {
+            serializer.serialize_bytes(&bytes)
+        }
+    }
+}
+
+struct U256Visitor;
+
+impl<'de> Visitor<'de> for U256Visitor {
+    type Value = U256;
+
+    fn expecting(&self, f: &mut Formatter) -> FmtResult {
+        write!(f, "a hex string or 32 big-endian bytes")
+    }
+
+    fn visit_str<E: Error>(self, value: &str) -> Result<U256, E> {
+        U256::from_hex(value).map_err(E::custom)
+    }
+
+    fn visit_bytes<E: Error>(self, value: &[u8]) -> Result<U256, E> {
+        let bytes: [u8; 32] = value
+            .try_into()
+            .map_err(|_| E::invalid_length(value.len(), &self))?;
+        Ok(U256::from_be_bytes(bytes))
+    }
+}
+
+impl<'de> Deserialize<'de> for U256 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(U256Visitor)
+        } else {
+            deserializer.deserialize_bytes(U256Visitor)
+        }
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct RawFieldElement<U> {
+    number: U,
+    prime: U,
+}
+
+impl<U: FieldUint + Serialize> Serialize for GenericFieldElement<U> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        RawFieldElement {
+            number: self.number,
+            prime: self.prime,
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, U: FieldUint + Deserialize<'de>> Deserialize<'de> for GenericFieldElement<U> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = RawFieldElement::<U>::deserialize(deserializer)?;
+        GenericFieldElement::new(raw.number, raw.prime).map_err(D::Error::custom)
+    }
+}
+
+impl Serialize for S256Field {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.number().serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for S256Field {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        S256Field::new(U256::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+impl<const P: u64> Serialize for Fp<P> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.number().serialize(serializer)
+    }
+}
+
+impl<'de, const P: u64> Deserialize<'de> for Fp<P> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Fp::new(u64::deserialize(deserializer)?).map_err(D::Error::custom)
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+#[serde(bound(
+    serialize = "G: GraphPoint + Serialize",
+    deserialize = "G: GraphPoint + Deserialize<'de>"
+))]
+struct RawPoint<G: GraphPoint> {
+    x: Coordinate<G>,
+    y: Coordinate<G>,
+    a: Coordinate<G>,
+    b: Coordinate<G>,
+}
+
+impl<G: GraphPoint + Serialize> Serialize for Point<G> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        RawPoint {
+            x: self.x,
+            y: self.y,
+            a: Coordinate::Value(self.a()),
+            b: Coordinate::Value(self.b()),
+        }
+        .serialize(serializer)
+    }
+}
+
+impl<'de, G: GraphPoint + Deserialize<'de>> Deserialize<'de> for Point<G> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let raw = RawPoint::<G>::deserialize(deserializer)?;
+        Point::new(raw.x, raw.y, raw.a, raw.b).map_err(D::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{field_element::FieldElement, real_value::RealValue};
+
+    #[test]
+    fn u256_is_serialized_as_padded_hex() {
+        let json = serde_json::to_string(&U256::from(255)).unwrap();
+        assert_eq!(json, format!("\"{}ff\"", "0".repeat(62)));
+        assert_eq!(
+            serde_json::from_str::<U256>(&json).unwrap(),
+            U256::from(255)
+        );
+    }
+
+    #[test]
+    fn field_element_roundtrips() {
+        let element = FieldElement::new(47, 223).unwrap();
+        let json = serde_json::to_string(&element).unwrap();
+        assert_eq!(
+            serde_json::from_str::<FieldElement>(&json).unwrap(),
+            element
+        );
+
+        let small = GenericFieldElement::<u32>::new(47u32, 223).unwrap();
+        let json = serde_json::to_string(&small).unwrap();
+        assert_eq!(json, r#"{"number":47,"prime":223}"#);
+    }
+
+    #[test]
+    fn cannot_deserialize_element_outside_of_field() {
+        let json = r#"{"number":300,"prime":223}"#;
+        assert!(serde_json::from_str::<GenericFieldElement<u32>>(json).is_err());
+    }
+
+    #[test]
+    fn points_roundtrip() {
+        let point = Point::from_finite_field(47, 71, 0, 7, 223).unwrap();
+        let json = serde_json::to_string(&point).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Point<FieldElement>>(&json).unwrap(),
+            point
+        );
+
+        let real = Point::new(-1, -1, 5, 7).unwrap();
+        let json = serde_json::to_string(&real).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Point<RealValue>>(&json).unwrap(),
+            real
+        );
+    }
+
+    #[test]
+    fn point_at_infinity_roundtrips() {
+        let infinity = Point::new::<Coordinate<RealValue>, i32>(
+            Coordinate::Infinity,
+            Coordinate::Infinity,
+            5,
+            7,
+        )
+        .unwrap();
+        let json = serde_json::to_string(&infinity).unwrap();
+        assert_eq!(
+            serde_json::from_str::<Point<RealValue>>(&json).unwrap(),
+            infinity
+        );
+    }
+
+    #[test]
+    fn cannot_deserialize_point_off_the_curve() {
+        let json = r#"{"x":{"Value":-1.0},"y":{"Value":-2.0},"a":{"Value":5.0},"b":{"Value":7.0}}"#;
+        assert!(serde_json::from_str::<Point<RealValue>>(json).is_err());
+    }
+
+    #[test]
+    fn cannot_deserialize_point_with_one_infinite_coordinate() {
+        let element = |n: u32| Coordinate::Value(FieldElement::new(n, 223).unwrap());
+        let raw = RawPoint {
+            x: Coordinate::Infinity,
+            y: element(26),
+            a: element(0),
+            b: element(7),
+        };
+        let json = serde_json::to_string(&raw).unwrap();
+        assert!(serde_json::from_str::<Point<FieldElement>>(&json).is_err());
+    }
+
+    #[test]
+    fn s256_field_and_fp_serialize_as_their_number() {
+        let element = S256Field::new(7).unwrap();
+        let json = serde_json::to_string(&element).unwrap();
+        assert_eq!(serde_json::from_str::<S256Field>(&json).unwrap(), element);
+
+        let element = Fp::<223>::new(47).unwrap();
+        assert_eq!(serde_json::to_string(&element).unwrap(), "47");
+        assert!(serde_json::from_str::<Fp<223>>("300").is_err());
+    }
+}
@@ -0,0 +1,209 @@
+//! Shamir secret sharing over a prime field. The secret is the constant term
+//! of a random polynomial of degree `threshold - 1`, and share `i` is the
+//! polynomial evaluated at `i`, so any `threshold` shares determine it.
+//! Private keys are shared by converting their [`crate::scalar::Scalar`] to an
+//! element modulo N and back.
+
+use crate::{
+    field_element::GenericFieldElement,
+    field_uint::FieldUint,
+    polynomial::{lagrange_interpolate, Polynomial},
+};
+
+/// Serialized shares store the index in a single byte.
+pub const MAX_SHARES: u8 = u8::MAX;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Share<U: FieldUint> {
+    pub index: u8,
+    pub value: GenericFieldElement<U>,
+}
+
+impl<U: FieldUint> Share<U> {
+    /// The index byte followed by the value as 32 big-endian bytes.
+    pub fn to_bytes(self) -> [u8; 33] {
+        let mut bytes = [0u8; 33];
+        bytes[0] = self.index;
+        bytes[1..].copy_from_slice(&self.value.to_bytes32());
+        bytes
+    }
+
+    pub fn from_bytes(bytes: &[u8], prime: U) -> Result<Self, String> {
+        let (&index, value) = bytes.split_first().ok_or("Share is empty")?;
+        if index == 0 {
+            return Err("Share index cannot be 0, that is where the secret is".to_string());
+        }
+
+        Ok(Share {
+            index,
+            value: GenericFieldElement::from_bytes(value, prime)?,
+        })
+    }
+}
+
+/// Splits `secret` into `shares` shares, any `threshold` of which recover
+/// it. `random` supplies the other coefficients; its values are reduced
+/// modulo the prime, so it should be uniform over a range much wider than
+/// the prime or already below it.
+pub fn split<U: FieldUint>(
+    secret: GenericFieldElement<U>,
+    threshold: u8,
+    shares: u8,
+    mut random: impl FnMut() -> U,
+) -> Result<Vec<Share<U>>, String> {
+    if threshold == 0 || threshold > shares {
+        return Err(format!(
+            "Threshold {} must be between 1 and the number of shares {}",
+            threshold, shares
+        ));
+    }
+
+    if U::from(shares as u32) >= secret.prime {
+        return Err(format!(
+            "{} shares need more distinct indexes than the field of order {} has",
+            shares, secret.prime
+        ));
+    }
+
+    let mut coefficients = vec![secret];
+    coefficients.extend((1..threshold).map(|_| {
+        GenericFieldElement::new_unchecked(random().rem_euclid(secret.prime), secret.prime)
+    }));
+    let polynomial = Polynomial::new(coefficients)?;
+
+    Ok((1..=shares)
+        .map(|index| Share {
+            index,
+            value: polynomial.evaluate(GenericFieldElement::new_unchecked(
+                U::from(index as u32),
+                secret.prime,
+            )),
+        })
+        .collect())
+}
+
+/// Recovers the secret from at least `threshold` shares. With fewer shares
+/// the result is a meaningless field element; that cannot be detected.
+pub fn combine<U: FieldUint>(shares: &[Share<U>]) -> Result<GenericFieldElement<U>, String> {
+    let first = shares.first().ok_or("At least one share is needed")?;
+    let prime = first.value.prime;
+
+    let points: Vec<_> = shares
+        .iter()
+        .map(|share| {
+            let x = U::from(share.index as u32);
+            if x >= prime {
+                return Err(format!(
+                    "Share index {} is not below the field order {}",
+                    share.index, prime
+                ));
+            }
+
+            Ok((GenericFieldElement::new_unchecked(x, prime), share.value))
+        })
+        .collect::<Result<_, String>>()?;
+
+    lagrange_interpolate(&points, GenericFieldElement::new_unchecked(U::ZERO, prime))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{field_element::FieldElement, s256_field::P, scalar::Scalar, u256::U256};
+
+    // Deterministic stand-in for a random number generator.
+    fn counter() -> impl FnMut() -> U256 {
+        let mut state = U256::from(12345);
+        move || {
+            state = state
+                .mul_mod(U256::from(1_103_515_245), P)
+                .add_mod(U256::from(12345), P);
+            state
+        }
+    }
+
+    fn secret() -> FieldElement {
+        FieldElement::new(U256::from_hex("deadbeefcafe").unwrap(), P).unwrap()
+    }
+
+    #[test]
+    fn any_threshold_shares_recover_secret() {
+        let shares = split(secret(), 3, 5, counter()).unwrap();
+        assert_eq!(shares.len(), 5);
+
+        for (a, b, c) in [(0, 1, 2), (0, 2, 4), (1, 3, 4), (4, 2, 0)] {
+            let subset = [shares[a], shares[b], shares[c]];
+            assert_eq!(combine(&subset), Ok(secret()));
+        }
+        assert_eq!(combine(&shares), Ok(secret()));
+    }
+
+    #[test]
+    fn scalars_are_shared_as_elements_modulo_n() {
+        let key = Scalar::from(0xdeadbeef);
+        let shares = split(key.into(), 2, 3, counter()).unwrap();
+        let recovered = combine(&shares[1..]).unwrap();
+        assert_eq!(Scalar::try_from(recovered), Ok(key));
+        assert!(Scalar::try_from(secret()).is_err());
+    }
+
+    #[test]
+    fn fewer_shares_than_threshold_do_not_recover_secret() {
+        let shares = split(secret(), 3, 5, counter()).unwrap();
+        assert_ne!(combine(&shares[..2]), Ok(secret()));
+    }
+
+    #[test]
+    fn threshold_of_one_hands_out_the_secret() {
+        let shares = split(secret(), 1, 3, counter()).unwrap();
+        assert!(shares.iter().all(|share| share.value == secret()));
+    }
+
+    #[test]
+    fn rejects_invalid_parameters() {
+        assert!(split(secret(), 0, 3, counter()).is_err());
+        assert!(split(secret(), 4, 3, counter()).is_err());
+
+        let small = GenericFieldElement::<u32>::new(3u32, 5).unwrap();
+        assert!(split(small, 2, 5, || 1).is_err());
+        assert!(split(small, 2, 4, || 1).is_ok());
+    }
+
+    #[test]
+    fn shares_roundtrip_through_bytes() {
+        let shares = split(secret(), 2, 3, counter()).unwrap();
+        let parsed: Vec<_> = shares
+            .iter()
+            .map(|share| Share::from_bytes(&share.to_bytes(), P).unwrap())
+            .collect();
+
+        assert_eq!(parsed, shares);
+        assert_eq!(combine(&parsed[1..]), Ok(secret()));
+    }
+
+    #[test]
+    fn cannot_parse_invalid_shares() {
+        assert!(Share::<U256>::from_bytes(&[], P).is_err());
+        assert!(Share::<U256>::from_bytes(&[0; 33], P).is_err());
+
+        let mut bytes = [0xff; 33];
+        bytes[0] = 1;
+        assert!(Share::<U256>::from_bytes(&bytes, P).is_err());
+    }
+
+    #[test]
+    fn cannot_combine_duplicate_shares() {
+        let shares = split(secret(), 2, 3, counter()).unwrap();
+        assert!(combine(&[shares[0], shares[0]]).is_err());
+        assert!(combine::<U256>(&[]).is_err());
+    }
+
+    #[test]
+    fn cannot_combine_shares_with_index_outside_of_field() {
+        let share = Share {
+            index: 7,
+            value: GenericFieldElement::<u32>::new(1u32, 5).unwrap(),
+        };
+        assert!(combine(&[share]).is_err());
+    }
+}
@@ -0,0 +1,93 @@
+//! Block subsidy schedule: 50 BTC per block, halved every 210,000 blocks
+//! until it rounds down to zero. Amounts are in satoshis.
+
+pub const HALVING_INTERVAL: u32 = 210_000;
+pub const INITIAL_SUBSIDY: u64 = 50 * 100_000_000;
+
+/// Number of halvings that have happened by `height`.
+pub fn halving_epoch(height: u32) -> u32 {
+    height / HALVING_INTERVAL
+}
+
+/// Height of the first block mined after the next halving.
+pub fn next_halving_height(height: u32) -> u32 {
+    (halving_epoch(height) + 1) * HALVING_INTERVAL
+}
+
+/// Amount a coinbase at `height` may create on top of the fees it collects.
+pub fn subsidy(height: u32) -> u64 {
+    // Shifting by 64 or more overflows, so Bitcoin Core special-cases it too.
+    match halving_epoch(height) {
+        halvings if halvings >= 64 => 0,
+        halvings => INITIAL_SUBSIDY >> halvings,
+    }
+}
+
+/// Checks that a coinbase paying out `value` does not claim more than the
+/// subsidy plus the `fees` of the block's other transactions.
+pub fn check_coinbase_value(height: u32, value: u64, fees: u64) -> Result<(), String> {
+    let allowed = subsidy(height) + fees;
+    if value > allowed {
+        return Err(format!(
+            "Coinbase at height {} pays {} but at most {} is allowed",
+            height, value, allowed
+        ));
+    }
+
+    Ok(())
+}
+
+/// Sum of the subsidies of blocks 0 through `height`. The genesis output is
+/// counted even though it can never be spent.
+pub fn total_supply_at(height: u32) -> u64 {
+    let epochs = halving_epoch(height);
+    let completed: u64 = (0..epochs)
+        .map(|epoch| subsidy(epoch * HALVING_INTERVAL) * HALVING_INTERVAL as u64)
+        .sum();
+    let current = (height % HALVING_INTERVAL + 1) as u64 * subsidy(height);
+
+    completed + current
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn subsidy_halves_every_interval() {
+        assert_eq!(subsidy(0), 5_000_000_000);
+        assert_eq!(subsidy(209_999), 5_000_000_000);
+        assert_eq!(subsidy(210_000), 2_500_000_000);
+        assert_eq!(subsidy(840_000), 312_500_000);
+        assert_eq!(subsidy(6_929_999), 1);
+        assert_eq!(subsidy(6_930_000), 0);
+        assert_eq!(subsidy(u32::MAX), 0);
+    }
+
+    #[test]
+    fn halving_heights() {
+        assert_eq!(halving_epoch(839_999), 3);
+        assert_eq!(halving_epoch(840_000), 4);
+        assert_eq!(next_halving_height(0), 210_000);
+        assert_eq!(next_halving_height(840_000), 1_050_000);
+    }
+
+    #[test]
+    fn coinbase_may_claim_subsidy_and_fees() {
+        assert!(check_coinbase_value(840_000, 312_500_000, 0).is_ok());
+        assert!(check_coinbase_value(840_000, 312_500_100, 100).is_ok());
+        assert!(check_coinbase_value(840_000, 312_500_000, 100).is_ok());
+        assert!(check_coinbase_value(840_000, 312_500_101, 100).is_err());
+    }
+
+    #[test]
+    fn total_supply_accumulates_subsidies() {
+        assert_eq!(total_supply_at(0), 5_000_000_000);
+        assert_eq!(total_supply_at(209_999), 210_000 * 5_000_000_000);
+        assert_eq!(
+            total_supply_at(210_000),
+            210_000 * 5_000_000_000 + 2_500_000_000
+        );
+        assert_eq!(total_supply_at(u32::MAX), 2_099_999_997_690_000);
+    }
+}
@@ -0,0 +1,21 @@
+/// Fallible counterparts of the arithmetic operators, for types whose
+/// operands may be incompatible (e.g. field elements of different primes).
+pub trait TryAdd<Rhs = Self> {
+    type Output;
+    fn try_add(self, rhs: Rhs) -> Result<Self::Output, String>;
+}
+
+pub trait TrySub<Rhs = Self> {
+    type Output;
+    fn try_sub(self, rhs: Rhs) -> Result<Self::Output, String>;
+}
+
+pub trait TryMul<Rhs = Self> {
+    type Output;
+    fn try_mul(self, rhs: Rhs) -> Result<Self::Output, String>;
+}
+
+pub trait TryDiv<Rhs = Self> {
+    type Output;
+    fn try_div(self, rhs: Rhs) -> Result<Self::Output, String>;
+}
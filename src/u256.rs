@@ -0,0 +1,639 @@
+use std::{
+    cmp::Ordering,
+    fmt::{Debug, Display, Formatter, LowerHex, Result as FmtResult, UpperHex},
+    ops::{Add, Div, Mul, Rem, Shl, Shr, Sub},
+};
+
+mod reduction;
+
+/// 256-bit unsigned integer stored as four little-endian 64-bit limbs.
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct U256([u64; 4]);
+
+impl U256 {
+    pub const ZERO: Self = U256([0, 0, 0, 0]);
+    pub const ONE: Self = U256([1, 0, 0, 0]);
+    pub const MAX: Self = U256([u64::MAX; 4]);
+
+    pub const fn from_limbs(limbs: [u64; 4]) -> Self {
+        U256(limbs)
+    }
+
+    pub const fn from_u64(value: u64) -> Self {
+        U256([value, 0, 0, 0])
+    }
+
+    pub const fn from_u128(value: u128) -> Self {
+        U256([value as u64, (value >> 64) as u64, 0, 0])
+    }
+
+    pub fn limbs(&self) -> [u64; 4] {
+        self.0
+    }
+
+    pub fn from_be_bytes(bytes: [u8; 32]) -> Self {
+        let mut limbs = [0u64; 4];
+        for (i, chunk) in bytes.chunks_exact(8).enumerate() {
+            limbs[3 - i] = u64::from_be_bytes(chunk.try_into().unwrap());
+        }
+
+        U256(limbs)
+    }
+
+    pub fn to_be_bytes(self) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        for (i, limb) in self.0.iter().rev().enumerate() {
+            bytes[i * 8..(i + 1) * 8].copy_from_slice(&limb.to_be_bytes());
+        }
+
+        bytes
+    }
+
+    pub fn from_hex(hex: &str) -> Result<Self, String> {
+        let digits = hex
+            .strip_prefix("0x")
+            .or_else(|| hex.strip_prefix("0X"))
+            .unwrap_or(hex);
+
+        if digits.is_empty() || digits.len() > 64 {
+            return Err(format!("{} is not a valid 256-bit hex number", hex));
+        }
+
+        let mut result = U256::ZERO;
+        for c in digits.chars() {
+            let digit = c
+                .to_digit(16)
+                .ok_or_else(|| format!("{} is not a valid 256-bit hex number", hex))?;
+            result = (result << 4) + U256::from(digit);
+        }
+
+        Ok(result)
+    }
+
+    pub fn to_u64(self) -> Option<u64> {
+        if self.0[1..].iter().all(|&limb| limb == 0) {
+            Some(self.0[0])
+        } else {
+            None
+        }
+    }
+
+    pub fn is_zero(&self) -> bool {
+        *self == U256::ZERO
+    }
+
+    pub fn is_odd(&self) -> bool {
+        self.0[0] & 1 == 1
+    }
+
+    pub fn bit(&self, index: u32) -> bool {
+        if index >= 256 {
+            return false;
+        }
+
+        (self.0[(index / 64) as usize] >> (index % 64)) & 1 == 1
+    }
+
+    /// Number of bits needed to represent the value, 0 for zero.
+    pub fn bits(&self) -> u32 {
+        for i in (0..4).rev() {
+            if self.0[i] != 0 {
+                return i as u32 * 64 + 64 - self.0[i].leading_zeros();
+            }
+        }
+
+        0
+    }
+
+    pub fn overflowing_add(self, other: Self) -> (Self, bool) {
+        let mut limbs = [0u64; 4];
+        let mut carry = false;
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            let (sum, c1) = self.0[i].overflowing_add(other.0[i]);
+            let (sum, c2) = sum.overflowing_add(carry as u64);
+            *limb = sum;
+            carry = c1 || c2;
+        }
+
+        (U256(limbs), carry)
+    }
+
+    pub fn overflowing_sub(self, other: Self) -> (Self, bool) {
+        let mut limbs = [0u64; 4];
+        let mut borrow = false;
+        for (i, limb) in limbs.iter_mut().enumerate() {
+            let (diff, b1) = self.0[i].overflowing_sub(other.0[i]);
+            let (diff, b2) = diff.overflowing_sub(borrow as u64);
+            *limb = diff;
+            borrow = b1 || b2;
+        }
+
+        (U256(limbs), borrow)
+    }
+
+    pub fn checked_add(self, other: Self) -> Option<Self> {
+        match self.overflowing_add(other) {
+            (sum, false) => Some(sum),
+            _ => None,
+        }
+    }
+
+    pub fn checked_sub(self, other: Self) -> Option<Self> {
+        match self.overflowing_sub(other) {
+            (diff, false) => Some(diff),
+            _ => None,
+        }
+    }
+
+    pub fn checked_mul(self, other: Self) -> Option<Self> {
+        match self.widening_mul(other) {
+            (low, high) if high.is_zero() => Some(low),
+            _ => None,
+        }
+    }
+
+    /// Full 512-bit product, returned as (low, high) halves.
+    pub fn widening_mul(self, other: Self) -> (Self, Self) {
+        let wide = mul_wide(&self.0, &other.0);
+        (
+            U256([wide[0], wide[1], wide[2], wide[3]]),
+            U256([wide[4], wide[5], wide[6], wide[7]]),
+        )
+    }
+
+    pub fn div_rem(self, divisor: Self) -> (Self, Self) {
+        let numerator = [self.0[0], self.0[1], self.0[2], self.0[3], 0, 0, 0, 0];
+        let (quotient, remainder) = div_rem_wide(&numerator, &divisor.0);
+        (
+            U256([quotient[0], quotient[1], quotient[2], quotient[3]]),
+            U256(remainder),
+        )
+    }
+
+    /// (self + other) mod modulus, for operands already reduced modulo `modulus`.
+    pub fn add_mod(self, other: Self, modulus: Self) -> Self {
+        let (sum, carry) = self.overflowing_add(other);
+        if carry || sum >= modulus {
+            sum.overflowing_sub(modulus).0
+        } else {
+            sum
+        }
+    }
+
+    /// (self - other) mod modulus, for operands already reduced modulo `modulus`.
+    pub fn sub_mod(self, other: Self, modulus: Self) -> Self {
+        if self >= other {
+            self - other
+        } else {
+            modulus - (other - self)
+        }
+    }
+
+    pub fn mul_mod(self, other: Self, modulus: Self) -> Self {
+        let wide = mul_wide(&self.0, &other.0);
+        U256(reduction::Reduction::for_modulus(&modulus.0).reduce(&wide, &modulus.0))
+    }
+}
+
+fn mul_wide(a: &[u64; 4], b: &[u64; 4]) -> [u64; 8] {
+    let mut result = [0u64; 8];
+    for i in 0..4 {
+        let mut carry = 0u128;
+        for j in 0..4 {
+            let t = a[i] as u128 * b[j] as u128 + result[i + j] as u128 + carry;
+            result[i + j] = t as u64;
+            carry = t >> 64;
+        }
+        result[i + 4] = carry as u64;
+    }
+
+    result
+}
+
+// Knuth's algorithm D (TAOCP 4.3.1) on 64-bit digits.
+fn div_rem_wide(numerator: &[u64; 8], divisor: &[u64; 4]) -> ([u64; 8], [u64; 4]) {
+    let n = divisor
+        .iter()
+        .rposition(|&limb| limb != 0)
+        .expect("attempt to divide by zero")
+        + 1;
+    let mut quotient = [0u64; 8];
+
+    if n == 1 {
+        let d = divisor[0] as u128;
+        let mut remainder = 0u128;
+        for i in (0..8).rev() {
+            let current = (remainder << 64) | numerator[i] as u128;
+            quotient[i] = (current / d) as u64;
+            remainder = current % d;
+        }
+        return (quotient, [remainder as u64, 0, 0, 0]);
+    }
+
+    let m = match numerator.iter().rposition(|&limb| limb != 0) {
+        Some(top) if top + 1 >= n => top + 1 - n,
+        _ => {
+            let mut remainder = [0u64; 4];
+            remainder.copy_from_slice(&numerator[..4]);
+            return (quotient, remainder);
+        }
+    };
+
+    let shift = divisor[n - 1].leading_zeros();
+    let mut vn = [0u64; 4];
+    for i in (1..n).rev() {
+        vn[i] = shl_digits(divisor[i], divisor[i - 1], shift);
+    }
+    vn[0] = divisor[0] << shift;
+
+    let mut un = [0u64; 9];
+    un[m + n] = shr_carry(numerator[m + n - 1], shift);
+    for i in (1..m + n).rev() {
+        un[i] = shl_digits(numerator[i], numerator[i - 1], shift);
+    }
+    un[0] = numerator[0] << shift;
+
+    let base = 1u128 << 64;
+    for j in (0..=m).rev() {
+        let top = ((un[j + n] as u128) << 64) | un[j + n - 1] as u128;
+        let mut qhat = top / vn[n - 1] as u128;
+        let mut rhat = top % vn[n - 1] as u128;
+
+        while qhat >= base || qhat * vn[n - 2] as u128 > ((rhat << 64) | un[j + n - 2] as u128) {
+            qhat -= 1;
+            rhat += vn[n - 1] as u128;
+            if rhat >= base {
+                break;
+            }
+        }
+
+        let mut borrow: i128 = 0;
+        for i in 0..n {
+            let product = qhat * vn[i] as u128;
+            let t = un[i + j] as i128 - borrow - (product as u64) as i128;
+            un[i + j] = t as u64;
+            borrow = (product >> 64) as i128 - (t >> 64);
+        }
+        let t = un[j + n] as i128 - borrow;
+        un[j + n] = t as u64;
+
+        quotient[j] = qhat as u64;
+        if t < 0 {
+            quotient[j] = quotient[j].wrapping_sub(1);
+            let mut carry = 0u128;
+            for i in 0..n {
+                let sum = un[i + j] as u128 + vn[i] as u128 + carry;
+                un[i + j] = sum as u64;
+                carry = sum >> 64;
+            }
+            un[j + n] = un[j + n].wrapping_add(carry as u64);
+        }
+    }
+
+    let mut remainder = [0u64; 4];
+    for i in 0..n - 1 {
+        remainder[i] = shr_digits(un[i + 1], un[i], shift);
+    }
+    remainder[n - 1] = un[n - 1] >> shift;
+
+    (quotient, remainder)
+}
+
+fn shl_digits(high: u64, low: u64, shift: u32) -> u64 {
+    if shift == 0 {
+        high
+    } else {
+        (high << shift) | (low >> (64 - shift))
+    }
+}
+
+fn shr_digits(high: u64, low: u64, shift: u32) -> u64 {
+    if shift == 0 {
+        low
+    } else {
+        (low >> shift) | (high << (64 - shift))
+    }
+}
+
+fn shr_carry(value: u64, shift: u32) -> u64 {
+    if shift == 0 {
+        0
+    } else {
+        value >> (64 - shift)
+    }
+}
+
+impl From<u32> for U256 {
+    fn from(value: u32) -> Self {
+        U256::from_u64(value as u64)
+    }
+}
+
+impl PartialOrd for U256 {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for U256 {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.0.iter().rev().cmp(other.0.iter().rev())
+    }
+}
+
+impl Add for U256 {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        self.checked_add(other)
+            .expect("attempt to add with overflow")
+    }
+}
+
+impl Sub for U256 {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        self.checked_sub(other)
+            .expect("attempt to subtract with overflow")
+    }
+}
+
+impl Mul for U256 {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        self.checked_mul(other)
+            .expect("attempt to multiply with overflow")
+    }
+}
+
+impl Div for U256 {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self::Output {
+        self.div_rem(other).0
+    }
+}
+
+impl Rem for U256 {
+    type Output = Self;
+
+    fn rem(self, other: Self) -> Self::Output {
+        self.div_rem(other).1
+    }
+}
+
+impl Shl<u32> for U256 {
+    type Output = Self;
+
+    /// Shifts left, dropping bits shifted past 256. Shifting by 256 or more yields zero.
+    fn shl(self, shift: u32) -> Self::Output {
+        if shift >= 256 {
+            return U256::ZERO;
+        }
+
+        let limb_shift = (shift / 64) as usize;
+        let bit_shift = shift % 64;
+        let mut limbs = [0u64; 4];
+        for (i, limb) in limbs.iter_mut().enumerate().skip(limb_shift) {
+            let low = if i > limb_shift {
+                self.0[i - limb_shift - 1]
+            } else {
+                0
+            };
+            *limb = shl_digits(self.0[i - limb_shift], low, bit_shift);
+        }
+
+        U256(limbs)
+    }
+}
+
+impl Shr<u32> for U256 {
+    type Output = Self;
+
+    /// Shifts right. Shifting by 256 or more yields zero.
+    fn shr(self, shift: u32) -> Self::Output {
+        if shift >= 256 {
+            return U256::ZERO;
+        }
+
+        let limb_shift = (shift / 64) as usize;
+        let bit_shift = shift % 64;
+        let mut limbs = [0u64; 4];
+        for (i, limb) in limbs.iter_mut().take(4 - limb_shift).enumerate() {
+            let high = if i + limb_shift + 1 < 4 {
+                self.0[i + limb_shift + 1]
+            } else {
+                0
+            };
+            *limb = shr_digits(high, self.0[i + limb_shift], bit_shift);
+        }
+
+        U256(limbs)
+    }
+}
+
+impl Display for U256 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        const CHUNK: U256 = U256::from_u64(10_000_000_000_000_000_000);
+
+        let mut chunks = Vec::new();
+        let mut rest = *self;
+        loop {
+            let (quotient, remainder) = rest.div_rem(CHUNK);
+            chunks.push(remainder.0[0]);
+            rest = quotient;
+            if rest.is_zero() {
+                break;
+            }
+        }
+
+        let mut digits = chunks.pop().unwrap().to_string();
+        for chunk in chunks.iter().rev() {
+            digits.push_str(&format!("{:019}", chunk));
+        }
+
+        f.pad_integral(true, "", &digits)
+    }
+}
+
+impl LowerHex for U256 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let digits = format!(
+            "{:x}{:016x}{:016x}{:016x}",
+            self.0[3], self.0[2], self.0[1], self.0[0]
+        );
+        let digits = digits.trim_start_matches('0');
+        f.pad_integral(true, "0x", if digits.is_empty() { "0" } else { digits })
+    }
+}
+
+impl UpperHex for U256 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let digits = format!("{:x}", self).to_uppercase();
+        f.pad_integral(true, "0x", &digits)
+    }
+}
+
+impl Debug for U256 {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        write!(f, "U256({})", self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::field_uint::FieldUint;
+
+    fn hex(value: &str) -> U256 {
+        U256::from_hex(value).unwrap()
+    }
+
+    const P: &str = "fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f";
+    const A: &str = "e9bb466a287385820942dc06bc69f2658575062102fbcd4f357fbc5af71a1bfc";
+    const B: &str = "25b2116aae6cff55ce0c3f08e12656f10e11160004524a7c3d2bd371fc80be13";
+
+    #[test]
+    fn hex_formatting_honours_width_and_prefix() {
+        let value = U256::from_limbs([0xdeadbeef, 0, 1, 0]);
+        assert_eq!(
+            format!("{:x}", value),
+            format!("1{}deadbeef", "0".repeat(24))
+        );
+        assert_eq!(
+            format!("{:X}", value),
+            format!("1{}DEADBEEF", "0".repeat(24))
+        );
+        assert_eq!(format!("{:x}", U256::ZERO), "0");
+        assert_eq!(format!("{:#x}", U256::from(255)), "0xff");
+        assert_eq!(
+            format!("{:064x}", U256::from(255)),
+            format!("{}ff", "0".repeat(62))
+        );
+        assert_eq!(format!("{:x}", U256::MAX), "f".repeat(64));
+    }
+
+    #[test]
+    fn can_parse_hex() {
+        assert_eq!(hex("0x1ff"), U256::from(511));
+        assert_eq!(hex(P).limbs()[3], u64::MAX);
+    }
+
+    #[test]
+    fn cannot_parse_invalid_hex() {
+        assert!(U256::from_hex("").is_err());
+        assert!(U256::from_hex("12g4").is_err());
+        assert!(U256::from_hex(&"1".repeat(65)).is_err());
+    }
+
+    #[test]
+    fn byte_conversion_roundtrips() {
+        let value = hex(A);
+        let bytes = value.to_be_bytes();
+        assert_eq!(bytes[0], 0xe9);
+        assert_eq!(bytes[31], 0xfc);
+        assert_eq!(U256::from_be_bytes(bytes), value);
+    }
+
+    #[test]
+    fn displays_as_decimal() {
+        let value = hex("77616364568c43961dfc388c3d5df9725e06e22dfff3f4ecb1dcec40db7aca58");
+        assert_eq!(
+            value.to_string(),
+            "53997299124689760827425712248361613396848508653951951780994131617513639430744"
+        );
+        assert_eq!(U256::ZERO.to_string(), "0");
+        assert_eq!(format!("{:>5}", U256::from(42)), "   42");
+    }
+
+    #[test]
+    fn ordering_compares_most_significant_limb_first() {
+        assert!(
+            U256::from_limbs([0, 0, 0, 1]) > U256::from_limbs([u64::MAX, u64::MAX, u64::MAX, 0])
+        );
+        assert!(U256::from(3) < U256::from(4));
+    }
+
+    #[test]
+    fn addition_overflow_is_detected() {
+        assert_eq!(U256::MAX.overflowing_add(U256::ONE), (U256::ZERO, true));
+        assert_eq!(U256::ZERO.overflowing_sub(U256::ONE), (U256::MAX, true));
+        assert!(U256::MAX.checked_mul(U256::from(2)).is_none());
+    }
+
+    #[test]
+    fn widening_multiplication_is_correct() {
+        let x = hex("77616364568c43961dfc388c3d5df9725e06e22dfff3f4ecb1dcec40db7aca58");
+        let (low, high) = x.widening_mul(hex(A));
+        assert_eq!(
+            low,
+            hex("47d99f4bdcb82bdecf8dffb3bdbeaaf9897eacb789d2f15e428eda1752676a0")
+        );
+        assert_eq!(
+            high,
+            hex("6cfef8700f6e8fb8c0837ec1f42009b71dbd421cb5607a4597d65646c07d9358")
+        );
+    }
+
+    #[test]
+    fn division_is_correct() {
+        let x = hex("77616364568c43961dfc388c3d5df9725e06e22dfff3f4ecb1dcec40db7aca58");
+        let y = hex("1646c2d6447d433985b11bb37b54c3950");
+        let (quotient, remainder) = x.div_rem(y);
+        assert_eq!(quotient, hex("55beac67310522df9a2dba636a67ccb3"));
+        assert_eq!(remainder, hex("1395cb900d4255756cd9cd47e2a52f768"));
+        assert_eq!(U256::from(100) / U256::from(7), U256::from(14));
+        assert_eq!(U256::from(100) % U256::from(7), U256::from(2));
+    }
+
+    #[test]
+    #[should_panic]
+    fn cannot_divide_by_zero() {
+        let _ = U256::ONE / U256::ZERO;
+    }
+
+    #[test]
+    fn shifts_move_bits_across_limbs() {
+        assert_eq!(U256::ONE << 255, U256::from_limbs([0, 0, 0, 1 << 63]));
+        assert_eq!((U256::ONE << 255) >> 255, U256::ONE);
+        assert_eq!(U256::from(0b1011) << 63 >> 63, U256::from(0b1011));
+        assert_eq!(U256::MAX >> 256, U256::ZERO);
+    }
+
+    #[test]
+    fn modular_inverse_is_correct() {
+        let (p, a) = (hex(P), hex(A));
+        assert_eq!(
+            a.inv_mod(p),
+            Some(hex(
+                "abd01f06da522bfe5d3bacb2e74a714c0c885efc8a5ff7307deb710670a605eb"
+            ))
+        );
+        assert_eq!(U256::from(3).inv_mod(U256::from(7)), Some(U256::from(5)));
+        assert_eq!(U256::from(4).inv_mod(U256::from(8)), None);
+        assert_eq!(U256::ZERO.inv_mod(p), None);
+    }
+
+    #[test]
+    fn modular_arithmetic_is_correct() {
+        let (p, a, b) = (hex(P), hex(A), hex(B));
+        assert_eq!(
+            a.mul_mod(b, p),
+            hex("ae6302a417f54e0a49296d0c44228ae0458aa49863491821f5cb757ab8f18482")
+        );
+        assert_eq!(
+            a.add_mod(b, p),
+            hex("f6d57d4d6e084d7d74f1b0f9d90495693861c21074e17cb72ab8fcdf39adde0")
+        );
+        assert_eq!(
+            b.sub_mod(a, p),
+            hex("3bf6cb0085f979d3c4c9630224bc648b889c0fdf01567d2d07ac171605669e46")
+        );
+        assert_eq!(
+            a.pow_mod(U256::from_u64(0x1234567890abcdef), p),
+            hex("fd8afa869dd59f84ad82674d2d7d7c1e5557905da85f657b152d4f8907d60aad")
+        );
+    }
+}
@@ -0,0 +1,213 @@
+//! Reduction of 512-bit products, with the strategy picked from the modulus.
+//! Moduli of the form 2^256 - c with a one-limb c, like the secp256k1 field
+//! prime, are folded without any division. The secp256k1 group order uses
+//! Barrett reduction with a precomputed reciprocal. Everything else falls back
+//! to long division.
+
+use super::{div_rem_wide, U256};
+
+const SECP256K1_N: [u64; 4] = [
+    0xBFD25E8CD0364141,
+    0xBAAEDCE6AF48A03B,
+    0xFFFFFFFFFFFFFFFE,
+    0xFFFFFFFFFFFFFFFF,
+];
+
+/// floor(2^512 / n) for the secp256k1 group order n.
+const SECP256K1_N_MU: [u64; 5] = [0x402DA1732FC9BEC0, 0x4551231950B75FC4, 1, 0, 1];
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub(super) enum Reduction {
+    /// Modulus 2^256 - c, since 2^256 = c mod the modulus.
+    PseudoMersenne(u64),
+    /// Modulus with its top limb set and floor(2^512 / modulus).
+    Barrett(&'static [u64; 5]),
+    Division,
+}
+
+impl Reduction {
+    pub(super) fn for_modulus(modulus: &[u64; 4]) -> Self {
+        if modulus[0] != 0 && modulus[1..].iter().all(|&limb| limb == u64::MAX) {
+            Reduction::PseudoMersenne(modulus[0].wrapping_neg())
+        } else if *modulus == SECP256K1_N {
+            Reduction::Barrett(&SECP256K1_N_MU)
+        } else {
+            Reduction::Division
+        }
+    }
+
+    pub(super) fn reduce(self, wide: &[u64; 8], modulus: &[u64; 4]) -> [u64; 4] {
+        match self {
+            Reduction::PseudoMersenne(c) => pseudo_mersenne(wide, c, modulus),
+            Reduction::Barrett(mu) => barrett(wide, mu, modulus),
+            Reduction::Division => div_rem_wide(wide, modulus).1,
+        }
+    }
+}
+
+fn pseudo_mersenne(wide: &[u64; 8], c: u64, modulus: &[u64; 4]) -> [u64; 4] {
+    // high * 2^256 + low = high * c + low, which fits in five limbs.
+    let mut folded = [0u64; 4];
+    let mut carry = 0u128;
+    for (i, limb) in folded.iter_mut().enumerate() {
+        let t = wide[i + 4] as u128 * c as u128 + wide[i] as u128 + carry;
+        *limb = t as u64;
+        carry = t >> 64;
+    }
+
+    // Fold the fifth limb the same way; it can overflow 2^256 at most once
+    // more, and adding c for that overflow cannot.
+    let top = carry * c as u128;
+    let (mut result, overflow) = U256(folded).overflowing_add(U256::from_u128(top));
+    if overflow {
+        result = result + U256::from_u64(c);
+    }
+
+    let modulus = U256(*modulus);
+    if result >= modulus {
+        result = result - modulus;
+    }
+
+    result.0
+}
+
+fn mul_limbs(a: &[u64], b: &[u64], out: &mut [u64]) {
+    for (i, &x) in a.iter().enumerate() {
+        let mut carry = 0u128;
+        for (j, &y) in b.iter().enumerate() {
+            if i + j >= out.len() {
+                break;
+            }
+            let t = x as u128 * y as u128 + out[i + j] as u128 + carry;
+            out[i + j] = t as u64;
+            carry = t >> 64;
+        }
+        if i + b.len() < out.len() {
+            out[i + b.len()] = carry as u64;
+        }
+    }
+}
+
+fn sub_limbs(a: &mut [u64; 5], b: &[u64; 5]) {
+    let mut borrow = false;
+    for (x, &y) in a.iter_mut().zip(b) {
+        let (difference, first) = x.overflowing_sub(y);
+        let (difference, second) = difference.overflowing_sub(borrow as u64);
+        *x = difference;
+        borrow = first || second;
+    }
+}
+
+fn at_least(a: &[u64; 5], b: &[u64; 5]) -> bool {
+    a.iter().rev().cmp(b.iter().rev()).is_ge()
+}
+
+// Handbook of Applied Cryptography, algorithm 14.42, with base 2^64 and
+// k = 4 limbs.
+fn barrett(wide: &[u64; 8], mu: &[u64; 5], modulus: &[u64; 4]) -> [u64; 4] {
+    let mut q2 = [0u64; 10];
+    mul_limbs(&wide[3..], mu, &mut q2);
+
+    let mut r2 = [0u64; 5];
+    mul_limbs(&q2[5..], modulus, &mut r2);
+
+    let mut result: [u64; 5] = wide[..5].try_into().unwrap();
+    sub_limbs(&mut result, &r2);
+
+    let modulus = [modulus[0], modulus[1], modulus[2], modulus[3], 0];
+    while at_least(&result, &modulus) {
+        sub_limbs(&mut result, &modulus);
+    }
+
+    result[..4].try_into().unwrap()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::mul_wide;
+    use super::*;
+
+    const P: [u64; 4] = [
+        0xFFFFFFFEFFFFFC2F,
+        0xFFFFFFFFFFFFFFFF,
+        0xFFFFFFFFFFFFFFFF,
+        0xFFFFFFFFFFFFFFFF,
+    ];
+
+    fn samples() -> Vec<[u64; 4]> {
+        let mut state = 0x9E3779B97F4A7C15u64;
+        let mut next = move || {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state
+        };
+
+        let mut values: Vec<[u64; 4]> =
+            (0..200).map(|_| [next(), next(), next(), next()]).collect();
+        values.extend([[0; 4], [1, 0, 0, 0], [u64::MAX; 4], P, SECP256K1_N]);
+        values
+    }
+
+    #[test]
+    fn picks_strategy_from_modulus() {
+        assert_eq!(
+            Reduction::for_modulus(&P),
+            Reduction::PseudoMersenne(0x1000003D1)
+        );
+        assert_eq!(
+            Reduction::for_modulus(&SECP256K1_N),
+            Reduction::Barrett(&SECP256K1_N_MU)
+        );
+        assert_eq!(Reduction::for_modulus(&[223, 0, 0, 0]), Reduction::Division);
+        assert_eq!(
+            Reduction::for_modulus(&[0, u64::MAX, u64::MAX, u64::MAX]),
+            Reduction::Division
+        );
+    }
+
+    #[test]
+    fn reciprocal_of_group_order_is_correct() {
+        // mu * n <= 2^512 < (mu + 1) * n
+        let mut product = [0u64; 9];
+        mul_limbs(&SECP256K1_N_MU, &SECP256K1_N, &mut product);
+        assert_eq!(product[8], 0);
+        let below = U256::from_limbs(product[4..8].try_into().unwrap());
+        assert_eq!(below, U256::MAX);
+
+        let mut next = [0u64; 9];
+        let mut mu_plus_one = SECP256K1_N_MU;
+        mu_plus_one[0] += 1;
+        mul_limbs(&mu_plus_one, &SECP256K1_N, &mut next);
+        assert_eq!(next[8], 1);
+    }
+
+    #[test]
+    fn strategies_match_long_division() {
+        let moduli = [
+            P,
+            SECP256K1_N,
+            [u64::MAX - 188, u64::MAX, u64::MAX, u64::MAX],
+            [u64::MAX; 4],
+        ];
+
+        for modulus in moduli {
+            let reduction = Reduction::for_modulus(&modulus);
+            assert_ne!(reduction, Reduction::Division);
+
+            for a in samples() {
+                for b in samples().iter().step_by(17) {
+                    let wide = mul_wide(&a, b);
+                    assert_eq!(
+                        reduction.reduce(&wide, &modulus),
+                        div_rem_wide(&wide, &modulus).1,
+                        "{:?} * {:?} mod {:?}",
+                        a,
+                        b,
+                        modulus
+                    );
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,199 @@
+//! Strict checks for points coming from outside, e.g. a peer's public key.
+//! Unlike the constructors, which return a message, these report why a point
+//! was refused so callers can react to each case.
+
+use std::{
+    error::Error,
+    fmt::{Display, Formatter, Result as FmtResult},
+};
+
+use crate::{
+    coordinate::Coordinate,
+    curve::{Curve, Parity},
+    field_element::FieldElement,
+    point::Point,
+    s256_field::S256Field,
+    s256_point::S256Point,
+    u256::U256,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PointError {
+    /// Wrong length or prefix byte.
+    InvalidEncoding,
+    /// The point at infinity, which is never a valid public key.
+    Infinity,
+    /// A coordinate is not below the field prime.
+    OutOfRange,
+    /// The coordinates do not satisfy the curve equation.
+    NotOnCurve,
+    /// x^3 + ax + b is not a square, so x belongs to the quadratic twist
+    /// instead of the curve.
+    OnTwist,
+    /// On the curve but outside the subgroup of the expected order.
+    NotInSubgroup,
+}
+
+impl Display for PointError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
+        let reason = match self {
+            PointError::InvalidEncoding => "invalid encoding",
+            PointError::Infinity => "point at infinity",
+            PointError::OutOfRange => "coordinate out of range",
+            PointError::NotOnCurve => "point not on the curve",
+            PointError::OnTwist => "point on the quadratic twist",
+            PointError::NotInSubgroup => "point not in the prime order subgroup",
+        };
+        write!(f, "Invalid point: {}", reason)
+    }
+}
+
+impl Error for PointError {}
+
+/// Rejects the point at infinity and, when the curve has a cofactor above
+/// one, points whose order does not divide `order`.
+pub fn validate_point(
+    point: Point<FieldElement>,
+    order: U256,
+    cofactor: u32,
+) -> Result<Point<FieldElement>, PointError> {
+    if point.x.is_infinity() {
+        return Err(PointError::Infinity);
+    }
+    if cofactor > 1 && !(order * point).x.is_infinity() {
+        return Err(PointError::NotInSubgroup);
+    }
+
+    Ok(point)
+}
+
+/// Builds the point (x, y) on `curve` with every check of [`validate_point`].
+pub fn validate_coordinates(
+    x: U256,
+    y: U256,
+    curve: Curve<FieldElement>,
+    order: U256,
+    cofactor: u32,
+) -> Result<Point<FieldElement>, PointError> {
+    let element = |n: U256| curve.element(n).map_err(|_| PointError::OutOfRange);
+    let (x, y) = (
+        Coordinate::Value(element(x)?),
+        Coordinate::Value(element(y)?),
+    );
+    let point = Point::on_curve(x, y, curve).map_err(|_| PointError::NotOnCurve)?;
+
+    validate_point(point, order, cofactor)
+}
+
+/// Parses a SEC 1 public key like [`S256Point::parse`], but refuses the
+/// encoding of the point at infinity. secp256k1 has cofactor one, so every
+/// point on it is in the subgroup.
+pub fn parse_public_key(bytes: &[u8]) -> Result<S256Point, PointError> {
+    let field = |bytes: &[u8]| {
+        S256Field::new(U256::from_be_bytes(bytes.try_into().unwrap()))
+            .map_err(|_| PointError::OutOfRange)
+    };
+
+    match (bytes.first(), bytes.len()) {
+        (Some(0x00), 1) => Err(PointError::Infinity),
+        (Some(0x04), 65) => S256Point::new(field(&bytes[1..33])?, field(&bytes[33..])?)
+            .map_err(|_| PointError::NotOnCurve),
+        (Some(&prefix @ (0x02 | 0x03)), 33) => {
+            let x = field(&bytes[1..])?;
+            let parity = if prefix == 0x03 {
+                Parity::Odd
+            } else {
+                Parity::Even
+            };
+            S256Point::from_x(x.number(), parity).map_err(|_| PointError::OnTwist)
+        }
+        _ => Err(PointError::InvalidEncoding),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::s256_field::P;
+
+    fn book_curve() -> Curve<FieldElement> {
+        Curve::from_finite_field(0, 7, 223).unwrap()
+    }
+
+    #[test]
+    fn accepts_valid_points() {
+        let point = validate_coordinates(47.into(), 71.into(), book_curve(), 21.into(), 12);
+        assert_eq!(point, Ok(book_curve().point_from_numbers(47, 71).unwrap()));
+
+        let key = 5001 * S256Point::G;
+        assert_eq!(parse_public_key(&key.sec(true)), Ok(key));
+        assert_eq!(parse_public_key(&(-key).sec(true)), Ok(-key));
+        assert_eq!(parse_public_key(&key.sec(false)), Ok(key));
+    }
+
+    #[test]
+    fn rejects_points_outside_of_subgroup() {
+        // (17, 56) has order 42, so it is not in the subgroup of order 21.
+        let point = validate_coordinates(17.into(), 56.into(), book_curve(), 21.into(), 12);
+        assert_eq!(point, Err(PointError::NotInSubgroup));
+        // Without a cofactor there is nothing to check.
+        assert!(validate_coordinates(17.into(), 56.into(), book_curve(), 21.into(), 1).is_ok());
+    }
+
+    #[test]
+    fn rejects_infinity() {
+        let infinity = book_curve().infinity();
+        assert_eq!(
+            validate_point(infinity, 21.into(), 12),
+            Err(PointError::Infinity)
+        );
+        assert_eq!(parse_public_key(&[0x00]), Err(PointError::Infinity));
+    }
+
+    #[test]
+    fn rejects_invalid_coordinates() {
+        let curve = book_curve();
+        assert_eq!(
+            validate_coordinates(223.into(), 71.into(), curve, 21.into(), 12),
+            Err(PointError::OutOfRange)
+        );
+        assert_eq!(
+            validate_coordinates(47.into(), 72.into(), curve, 21.into(), 12),
+            Err(PointError::NotOnCurve)
+        );
+
+        let mut off_curve = S256Point::G.sec(false);
+        off_curve[64] ^= 1;
+        assert_eq!(parse_public_key(&off_curve), Err(PointError::NotOnCurve));
+
+        let mut too_large = vec![0x02];
+        too_large.extend(P.to_be_bytes());
+        assert_eq!(parse_public_key(&too_large), Err(PointError::OutOfRange));
+    }
+
+    #[test]
+    fn rejects_twist_points() {
+        // 5^3 + 7 is not a square modulo p.
+        let mut twist = vec![0x03];
+        twist.extend(U256::from(5).to_be_bytes());
+        assert_eq!(parse_public_key(&twist), Err(PointError::OnTwist));
+    }
+
+    #[test]
+    fn rejects_malformed_encodings() {
+        let sec = S256Point::G.sec(true);
+        assert_eq!(parse_public_key(&[]), Err(PointError::InvalidEncoding));
+        assert_eq!(
+            parse_public_key(&sec[..32]),
+            Err(PointError::InvalidEncoding)
+        );
+        assert_eq!(
+            parse_public_key(&[&[0x04], &sec[1..]].concat()),
+            Err(PointError::InvalidEncoding)
+        );
+        assert_eq!(
+            PointError::OnTwist.to_string(),
+            "Invalid point: point on the quadratic twist"
+        );
+    }
+}
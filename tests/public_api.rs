@@ -0,0 +1,62 @@
+use programming_bitcoin::{prelude::*, s256_point::N, validation::parse_public_key};
+
+#[test]
+fn field_arithmetic() {
+    let a = FieldElement::new(7, 13).unwrap();
+    let b = FieldElement::new(12, 13).unwrap();
+    assert_eq!(a + b, FieldElement::new(6, 13).unwrap());
+    assert_eq!(a * b, FieldElement::new(6, 13).unwrap());
+    assert_eq!(a.pow(-3), FieldElement::new(8, 13).unwrap());
+    assert!(FieldElement::new(13, 13).is_err());
+}
+
+#[test]
+fn points_over_finite_field() {
+    let curve = Curve::from_finite_field(0, 7, 223).unwrap();
+    let p1 = curve.point_from_numbers(170, 142).unwrap();
+    let p2 = curve.point_from_numbers(60, 139).unwrap();
+    assert_eq!(
+        (p1 + p2).unwrap(),
+        curve.point_from_numbers(220, 181).unwrap()
+    );
+
+    let generator = curve.point_from_numbers(47, 71).unwrap();
+    assert_eq!(generator.multiples().count(), 21);
+    assert_eq!(21 * generator, curve.infinity());
+    assert_eq!(generator.order(), Ok(21));
+}
+
+#[test]
+fn points_over_reals() {
+    let point = Point::new(-1, -1, 5, 7).unwrap();
+    assert_eq!((point + point).unwrap(), Point::new(18, 77, 5, 7).unwrap());
+    assert!(Point::<RealValue>::new(-1, -2, 5, 7).is_err());
+}
+
+#[test]
+fn secp256k1_keys_and_signatures() {
+    let secret = Scalar::from(12345);
+    let public = secret * S256Point::G;
+
+    let compressed = public.sec(true);
+    assert_eq!(compressed.len(), 33);
+    assert_eq!(S256Point::parse(&compressed), Ok(public));
+    assert_eq!(parse_public_key(&public.sec(false)), Ok(public));
+
+    // Sign with a fixed nonce and check the signature verifies.
+    let z =
+        U256::from_hex("969f6056aa26f7d2795fd013fe88868d09c9f6aed96965016e1936ae47060d48").unwrap();
+    let k = Scalar::from(1234567890);
+    let r = (k * S256Point::G).x().unwrap().number() % N;
+    let s = (Scalar::reduce(z) + Scalar::reduce(r) * secret) * k.invert().unwrap();
+    assert!(public.verify(z, r, s.value()));
+    assert!(!public.verify(z, r, (s + Scalar::ONE).value()));
+}
+
+#[test]
+fn named_curves_share_generic_code() {
+    let generic: Point<FieldElement> = S256Point::G.into();
+    assert_eq!(Secp256k1::generator(), generic);
+    assert_eq!(generic.curve().prime(), Secp256k1::P);
+    assert!((P256::N * P256::generator()).x.is_infinity());
+}